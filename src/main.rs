@@ -1,8 +1,27 @@
 #![allow(unused)]
 
+use std::{env, process::ExitCode};
+
+use reader::CLI;
+
 mod processing;
+mod project;
 mod reader;
+#[cfg(test)]
+mod temp_dir;
 
-fn main() {
+fn main() -> ExitCode {
+    let mut cli = CLI::default();
+    let result = cli
+        .get_args(env::args().collect())
+        .and_then(|_| cli.run());
 
+    match result {
+        // exit only takes codes from 0 to 255
+        Ok(code) => ExitCode::from(code as u8),
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,101 @@
+use std::{
+    cell::RefCell,
+    io::{BufRead, Read, Write},
+    rc::Rc,
+};
+
+/// Where the text written by `print` and `println` ends up
+pub trait OutputSink {
+    fn write(&mut self, text: &str);
+}
+
+/// Writes straight to the process' stdout
+#[derive(Default)]
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn write(&mut self, text: &str) {
+        let mut stdout = std::io::stdout();
+        // a closed stdout is not something a script can do anything about
+        let _ = stdout.write_all(text.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+/// Collects everything written into a shared buffer
+///
+/// Clones share the same buffer, so a clone can be handed to the parser while the original is kept to read it back
+#[derive(Default, Clone)]
+pub struct BufferSink {
+    buffer: Rc<RefCell<String>>,
+}
+
+impl BufferSink {
+    pub fn contents(&self) -> String {
+        self.buffer.borrow().clone()
+    }
+}
+
+impl OutputSink for BufferSink {
+    fn write(&mut self, text: &str) {
+        self.buffer.borrow_mut().push_str(text);
+    }
+}
+
+/// Where `input`, `read_line` and `read_all` read from
+pub trait InputSource {
+    /// Reads the next line without its line ending, `None` once there is nothing left to read
+    fn read_line(&mut self) -> std::io::Result<Option<String>>;
+
+    /// Reads everything that is left
+    fn read_all(&mut self) -> std::io::Result<String>;
+}
+
+/// Reads from the process' stdin
+#[derive(Default)]
+pub struct StdinSource;
+
+impl InputSource for StdinSource {
+    fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        ReaderSource::new(std::io::stdin().lock()).read_line()
+    }
+
+    fn read_all(&mut self) -> std::io::Result<String> {
+        ReaderSource::new(std::io::stdin().lock()).read_all()
+    }
+}
+
+/// Reads from anything buffered, like a `std::io::Cursor` over scripted input
+pub struct ReaderSource<R: BufRead> {
+    reader: R,
+}
+
+impl<R: BufRead> ReaderSource<R> {
+    pub fn new(reader: R) -> ReaderSource<R> {
+        ReaderSource { reader }
+    }
+}
+
+impl<R: BufRead> InputSource for ReaderSource<R> {
+    fn read_line(&mut self) -> std::io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+
+        Ok(Some(line))
+    }
+
+    fn read_all(&mut self) -> std::io::Result<String> {
+        let mut contents = String::new();
+        self.reader.read_to_string(&mut contents)?;
+        Ok(contents)
+    }
+}
@@ -9,11 +9,18 @@ use crate::processing::syntax_elements::Variable;
 use crate::processing::types::Line;
 use crate::processing::types::LineTokenizedBody;
 use crate::processing::types::VecPosition;
+use crate::processing::utils::variables::slice_variable_dec;
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum BaseLexingReturn {
     Int(i32),
     Variable(Variable),
+    /// Value given back by a builtin, like the `Str` from `read_line`
+    Value(DataTypes),
+    /// The line ran but has no value, like a `print`
+    Empty,
+    /// The script asked to stop with this status
+    Exit(i32),
 }
 
 /// Trait that has base functions required for each branch of the lexer
@@ -24,11 +31,23 @@ pub(super) trait BaseLexing {
 
     /// Checks if the line is assigning what we are attempting to do to a variable
     fn is_assigning_to_variable(&self, line: &Line) -> bool {
-        match line.tokenized_body.first() {
-            Some(Token::MutVarDeclaration(_)) => true,
-            Some(Token::ImmutVarDeclaration(_)) => true,
-            _ => false,
+        matches!(
+            line.tokenized_body.first(),
+            Some(Token::MutVarDeclaration(_)) | Some(Token::ImmutVarDeclaration(_))
+        )
+    }
+
+    /// The tokens of the line without the variable declaration, if there is one
+    ///
+    /// EXAMPLE:
+    ///     "set x = read_line"
+    ///     ["read_line"]
+    fn statement(&self, line: &Line) -> Result<LineTokenizedBody> {
+        if self.is_assigning_to_variable(line) {
+            return slice_variable_dec(line);
         }
+
+        Ok(line.tokenized_body.to_vec())
     }
 
     fn vec_to_string(&self, tokens: Vec<Token>) -> String {
@@ -40,5 +59,5 @@ pub(super) trait BaseLexing {
     }
 
 
-    fn execute(&self, line: &Line) -> Result<BaseLexingReturn>;
+    fn execute(&mut self, line: &Line) -> Result<BaseLexingReturn>;
 }
@@ -0,0 +1,67 @@
+use super::super::{Error, Result};
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+use std::cmp::Ordering;
+
+/// Runs the builtins of the `array` namespace, each gives back a new array
+pub(super) fn call(builtin: Builtin, pos: &Position, args: &[DataTypes]) -> Result<DataTypes> {
+    let values = expect_array(&args[0], pos)?;
+
+    match builtin {
+        Builtin::ArraySort => {
+            // sort_by can't fail part way, so check everything can be compared first
+            if let Some(first) = values.first() {
+                for value in values {
+                    compare(first, value, pos)?;
+                }
+            }
+
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| compare(a, b, pos).unwrap_or(Ordering::Equal));
+            Ok(DataTypes::Array(sorted))
+        }
+        Builtin::ArrayReverse => Ok(DataTypes::Array(values.iter().rev().cloned().collect())),
+        // stops at the end of the shorter array
+        Builtin::ArrayZip => {
+            let other = expect_array(&args[1], pos)?;
+            let pairs = values
+                .iter()
+                .zip(other)
+                .map(|(a, b)| DataTypes::Array(vec![a.clone(), b.clone()]))
+                .collect();
+            Ok(DataTypes::Array(pairs))
+        }
+        Builtin::ArrayEnumerate => {
+            let mut pairs = Vec::new();
+            for (index, value) in values.iter().enumerate() {
+                let index = match i32::try_from(index) {
+                    Ok(index) => index,
+                    Err(_) => return Err(Error::Overflow(builtin, pos.clone())),
+                };
+                pairs.push(DataTypes::Array(vec![DataTypes::Int(index), value.clone()]));
+            }
+            Ok(DataTypes::Array(pairs))
+        }
+        other => unreachable!("{} is not part of array", other),
+    }
+}
+
+pub(super) fn expect_array<'v>(value: &'v DataTypes, pos: &Position) -> Result<&'v [DataTypes]> {
+    match value {
+        DataTypes::Array(values) => Ok(values),
+        other => Err(Error::UnexpectedType("array", other.clone(), pos.clone())),
+    }
+}
+
+/// Orders two values of the same type, ints and floats can be compared with each other
+fn compare(a: &DataTypes, b: &DataTypes, pos: &Position) -> Result<Ordering> {
+    match (a, b) {
+        (DataTypes::Int(a), DataTypes::Int(b)) => Ok(a.cmp(b)),
+        (DataTypes::Float(a), DataTypes::Float(b)) => Ok(a.total_cmp(b)),
+        (DataTypes::Int(a), DataTypes::Float(b)) => Ok((*a as f64).total_cmp(b)),
+        (DataTypes::Float(a), DataTypes::Int(b)) => Ok(a.total_cmp(&(*b as f64))),
+        (DataTypes::Str(a), DataTypes::Str(b)) => Ok(a.cmp(b)),
+        (DataTypes::Char(a), DataTypes::Char(b)) => Ok(a.cmp(b)),
+        (DataTypes::Bool(a), DataTypes::Bool(b)) => Ok(a.cmp(b)),
+        (a, b) => Err(Error::UnexpectedType(a.type_name(), b.clone(), pos.clone())),
+    }
+}
@@ -0,0 +1,244 @@
+use super::super::{Error, Result};
+use super::expect_str;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Runs the builtins of the `csv` namespace
+pub(super) fn call(builtin: Builtin, pos: &Position, args: &[DataTypes]) -> Result<DataTypes> {
+    let delimiter = match args.get(1) {
+        Some(delimiter) => read_delimiter(delimiter, pos)?,
+        None => ',',
+    };
+
+    match builtin {
+        Builtin::CsvParse => {
+            let rows = parse(expect_str(&args[0], pos)?, delimiter, pos)?;
+            Ok(DataTypes::Array(
+                rows.into_iter()
+                    .map(|(_, fields)| to_array(fields))
+                    .collect(),
+            ))
+        }
+        Builtin::CsvParseRecords => {
+            let mut rows = parse(expect_str(&args[0], pos)?, delimiter, pos)?.into_iter();
+            let (header_line, header) = match rows.next() {
+                Some(header) => header,
+                None => return Ok(DataTypes::Array(Vec::new())),
+            };
+            // a map can only have each key once
+            for (index, name) in header.iter().enumerate() {
+                if header[..index].contains(name) {
+                    let message = format!("Header {:?} is there more than once", name);
+                    return Err(Error::InvalidCsv(message, header_line, pos.clone()));
+                }
+            }
+
+            let mut records = Vec::new();
+            for (line, fields) in rows {
+                if fields.len() != header.len() {
+                    let message = format!(
+                        "Expected {} fields like the header but found {}",
+                        header.len(),
+                        fields.len()
+                    );
+                    return Err(Error::InvalidCsv(message, line, pos.clone()));
+                }
+
+                let entries = header
+                    .iter()
+                    .cloned()
+                    .zip(fields.into_iter().map(DataTypes::Str))
+                    .collect();
+                records.push(DataTypes::Map(entries));
+            }
+
+            Ok(DataTypes::Array(records))
+        }
+        Builtin::CsvStringify => {
+            let rows = match &args[0] {
+                DataTypes::Array(rows) => rows,
+                other => return Err(Error::UnexpectedType("array", other.clone(), pos.clone())),
+            };
+
+            let mut csv = String::new();
+            // maps are written under a header made of the keys of the first one
+            if let Some(DataTypes::Map(first)) = rows.first() {
+                let header: Vec<String> = first.iter().map(|(key, _)| key.to_string()).collect();
+                write_row(&mut csv, header.iter().map(|key| key.as_str()), delimiter);
+
+                for row in rows {
+                    let entries = match row {
+                        DataTypes::Map(entries) => entries,
+                        other => {
+                            return Err(Error::UnexpectedType("map", other.clone(), pos.clone()))
+                        }
+                    };
+
+                    let mut fields = Vec::new();
+                    for key in &header {
+                        match entries.iter().find(|(existing, _)| existing == key) {
+                            Some((_, value)) => fields.push(field(value, pos)?),
+                            None => return Err(Error::MissingKey(key.to_string(), pos.clone())),
+                        }
+                    }
+                    write_row(
+                        &mut csv,
+                        fields.iter().map(|field| field.as_str()),
+                        delimiter,
+                    );
+                }
+
+                return Ok(DataTypes::Str(csv));
+            }
+
+            for row in rows {
+                let values = match row {
+                    DataTypes::Array(values) => values,
+                    other => {
+                        return Err(Error::UnexpectedType("array", other.clone(), pos.clone()))
+                    }
+                };
+
+                let fields = values
+                    .iter()
+                    .map(|value| field(value, pos))
+                    .collect::<Result<Vec<String>>>()?;
+                write_row(
+                    &mut csv,
+                    fields.iter().map(|field| field.as_str()),
+                    delimiter,
+                );
+            }
+
+            Ok(DataTypes::Str(csv))
+        }
+        other => unreachable!("{} is not part of csv", other),
+    }
+}
+
+/// The delimiter has to be a single char that can't be confused with quoting or the end of a row
+fn read_delimiter(delimiter: &DataTypes, pos: &Position) -> Result<char> {
+    let delimiter = match delimiter {
+        DataTypes::Char(c) => *c,
+        DataTypes::Str(text) => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(Error::InvalidDelimiter(text.to_string(), pos.clone())),
+            }
+        }
+        other => return Err(Error::UnexpectedType("str", other.clone(), pos.clone())),
+    };
+
+    match delimiter {
+        '"' | '\n' | '\r' => Err(Error::InvalidDelimiter(delimiter.to_string(), pos.clone())),
+        _ => Ok(delimiter),
+    }
+}
+
+fn to_array(fields: Vec<String>) -> DataTypes {
+    DataTypes::Array(fields.into_iter().map(DataTypes::Str).collect())
+}
+
+/// Splits the text into rows of fields, each along with the line it starts on, counting from 1
+///
+/// Fields in quotes can hold the delimiter, new lines and `""` for a quote. A new line at the very end does not start
+/// another row
+///
+/// EXAMPLE:
+///     "a,\"b,c\"\n1,\"say \"\"hi\"\"\"" -> [(1, ["a", "b,c"]), (2, ["1", "say \"hi\""])]
+fn parse(text: &str, delimiter: char, pos: &Position) -> Result<Vec<(usize, Vec<String>)>> {
+    let invalid =
+        |message: &str, line: usize| Error::InvalidCsv(message.to_string(), line, pos.clone());
+
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut row_line = 1;
+    // whether anything was read since the last row ended
+    let mut in_row = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        in_row = true;
+        match c {
+            '"' if field.is_empty() => {
+                let quote_line = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(invalid("Unterminated quoted field", quote_line)),
+                    }
+                }
+
+                match chars.peek() {
+                    None | Some('\n' | '\r') => {}
+                    Some(c) if *c == delimiter => {}
+                    Some(_) => return Err(invalid("Unexpected text after a quoted field", line)),
+                }
+            }
+            '"' => {
+                return Err(invalid(
+                    "Unexpected quote inside a field without quotes",
+                    line,
+                ))
+            }
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                fields.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut fields)));
+                line += 1;
+                row_line = line;
+                in_row = false;
+            }
+            c => field.push(c),
+        }
+    }
+
+    if in_row {
+        fields.push(field);
+        rows.push((row_line, fields));
+    }
+
+    Ok(rows)
+}
+
+/// The text of a value in a field, which can't hold arrays or maps
+fn field(value: &DataTypes, pos: &Position) -> Result<String> {
+    match value {
+        DataTypes::None => Ok(String::new()),
+        DataTypes::Array(_) | DataTypes::Map(_) => {
+            Err(Error::UnexpectedType("str", value.clone(), pos.clone()))
+        }
+        other => Ok(other.to_string()),
+    }
+}
+
+/// Writes the fields as a row, quoting the ones that need it
+fn write_row<'f>(csv: &mut String, fields: impl Iterator<Item = &'f str>, delimiter: char) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            csv.push(delimiter);
+        }
+
+        if field.contains([delimiter, '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push('\n');
+}
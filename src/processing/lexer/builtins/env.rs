@@ -0,0 +1,55 @@
+use super::super::{Error, Result};
+use super::expect_str;
+use crate::processing::permissions::Permissions;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Runs the builtins of the `env` namespace
+///
+/// `env.args` needs `--allow-args`, everything else `--allow-env`
+pub(super) fn call(
+    builtin: Builtin,
+    pos: &Position,
+    args: &[DataTypes],
+    permissions: &Permissions,
+    script_args: &[String],
+) -> Result<DataTypes> {
+    if builtin == Builtin::EnvArgs {
+        if !permissions.args {
+            return Err(Error::NotAllowed("env.args", "--allow-args", pos.clone()));
+        }
+
+        let script_args = script_args.iter().cloned().map(DataTypes::Str).collect();
+        return Ok(DataTypes::Array(script_args));
+    }
+
+    if !permissions.env {
+        return Err(Error::NotAllowed(
+            builtin.name(),
+            "--allow-env",
+            pos.clone(),
+        ));
+    }
+
+    match builtin {
+        Builtin::EnvGet => {
+            let name = expect_str(&args[0], pos)?;
+            match std::env::var(name) {
+                Ok(value) => Ok(DataTypes::Str(value)),
+                Err(_) => Err(Error::EnvNotSet(name.to_string(), pos.clone())),
+            }
+        }
+        Builtin::EnvHas => {
+            let name = expect_str(&args[0], pos)?;
+            Ok(DataTypes::Bool(std::env::var(name).is_ok()))
+        }
+        Builtin::EnvCwd => match std::env::current_dir() {
+            Ok(directory) => Ok(DataTypes::Str(directory.display().to_string())),
+            Err(e) => Err(Error::FileSystem(
+                ".".to_string(),
+                e.to_string(),
+                pos.clone(),
+            )),
+        },
+        other => unreachable!("{} is not part of env", other),
+    }
+}
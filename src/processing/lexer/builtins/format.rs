@@ -0,0 +1,280 @@
+use super::super::{Error, Result};
+use crate::processing::syntax_elements::{DataTypes, Position, Variable};
+
+/// Widest padding and most decimals a specifier can ask for, so a typo can't ask for gigabytes of spaces
+const MAX_WIDTH: usize = 1024;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Kind {
+    Display,
+    Hex,
+    UpperHex,
+    Binary,
+    Octal,
+}
+
+/// Everything after the `:` of a `{}`, laid out as `[[fill]align][+][0][width][.precision][kind]`
+///
+/// EXAMPLE:
+///     "*^+08.2" -> fill '*', centered, with a sign, zero padded to 8 with 2 decimals
+#[derive(PartialEq, Debug)]
+struct Spec {
+    fill: char,
+    align: Option<Align>,
+    sign: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    kind: Kind,
+}
+
+impl Default for Spec {
+    fn default() -> Spec {
+        Spec {
+            fill: ' ',
+            align: None,
+            sign: false,
+            zero: false,
+            width: 0,
+            precision: None,
+            kind: Kind::Display,
+        }
+    }
+}
+
+/// Fills in the `{}` parts of the template, `{{` and `}}` stand for the braces themselves
+///
+/// `{}` takes the next argument, `{1}` the argument at that index and `{name}` the variable with that name
+///
+/// EXAMPLE:
+///     "{:>6.2}|{0:x}|{name}" with 255.0, name = "ada"
+///     "255.00|ff|ada"
+pub(super) fn format(
+    template: &str,
+    args: &[DataTypes],
+    variables: &[Variable],
+    pos: &Position,
+) -> Result<String> {
+    let invalid = |message: String, index: usize| Error::InvalidFormat(message, pos.clone(), index);
+    let mut formatted = String::new();
+    let mut next_arg = 0;
+    let mut chars = template.chars().enumerate().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek().map(|(_, next)| *next) == Some(c) => {
+                chars.next();
+                formatted.push(c);
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                let mut closed = false;
+                for (_, c) in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    placeholder.push(c);
+                }
+
+                if !closed {
+                    return Err(invalid("Missing } for the {".to_string(), index));
+                }
+
+                let (name, spec) = match placeholder.split_once(':') {
+                    Some((name, spec)) => (name, spec),
+                    None => (placeholder.as_str(), ""),
+                };
+
+                let value = match name {
+                    "" => {
+                        next_arg += 1;
+                        args.get(next_arg - 1).ok_or_else(|| {
+                            invalid(format!("No argument {}", next_arg - 1), index)
+                        })?
+                    }
+                    _ if name.chars().all(|c| c.is_ascii_digit()) => {
+                        let arg = name.parse::<usize>().unwrap_or(usize::MAX);
+                        args.get(arg)
+                            .ok_or_else(|| invalid(format!("No argument {}", name), index))?
+                    }
+                    _ => match variables.iter().find(|v| v.name == name) {
+                        Some(variable) => &variable.value,
+                        None => return Err(invalid(format!("No variable named {}", name), index)),
+                    },
+                };
+
+                let spec = parse_spec(spec).map_err(|message| invalid(message, index))?;
+                let text = format_value(value, &spec).map_err(|message| invalid(message, index))?;
+                formatted.push_str(&text);
+            }
+            '}' => {
+                return Err(invalid(
+                    "Unmatched }, use }} for a single }".to_string(),
+                    index,
+                ))
+            }
+            _ => formatted.push(c),
+        }
+    }
+
+    Ok(formatted)
+}
+
+/// Reads the part of a `{}` after the `:`, the error being the message to report
+fn parse_spec(spec: &str) -> std::result::Result<Spec, String> {
+    let invalid = || format!("Invalid format specifier :{}", spec);
+    let chars: Vec<char> = spec.chars().collect();
+    let mut parsed = Spec::default();
+    let mut i = 0;
+
+    let to_align = |c: char| match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    };
+
+    match (chars.first(), chars.get(1).and_then(|c| to_align(*c))) {
+        (Some(fill), Some(align)) => {
+            parsed.fill = *fill;
+            parsed.align = Some(align);
+            i = 2;
+        }
+        (Some(c), None) if to_align(*c).is_some() => {
+            parsed.align = to_align(*c);
+            i = 1;
+        }
+        _ => {}
+    }
+
+    if chars.get(i) == Some(&'+') {
+        parsed.sign = true;
+        i += 1;
+    }
+
+    if chars.get(i) == Some(&'0') {
+        parsed.zero = true;
+        i += 1;
+    }
+
+    let width = digits(&chars[i..]);
+    if !width.is_empty() {
+        parsed.width = width.parse().map_err(|_| invalid())?;
+        if parsed.width > MAX_WIDTH {
+            return Err(format!(
+                "Width {} is over the limit of {}",
+                width, MAX_WIDTH
+            ));
+        }
+        i += width.len();
+    }
+
+    if chars.get(i) == Some(&'.') {
+        let precision = digits(&chars[i + 1..]);
+        let value = precision.parse().map_err(|_| invalid())?;
+        if value > MAX_WIDTH {
+            return Err(format!(
+                "Precision {} is over the limit of {}",
+                precision, MAX_WIDTH
+            ));
+        }
+        parsed.precision = Some(value);
+        i += precision.len() + 1;
+    }
+
+    parsed.kind = match &chars[i..] {
+        [] => Kind::Display,
+        ['x'] => Kind::Hex,
+        ['X'] => Kind::UpperHex,
+        ['b'] => Kind::Binary,
+        ['o'] => Kind::Octal,
+        _ => return Err(invalid()),
+    };
+
+    Ok(parsed)
+}
+
+fn digits(chars: &[char]) -> String {
+    chars.iter().take_while(|c| c.is_ascii_digit()).collect()
+}
+
+fn format_value(value: &DataTypes, spec: &Spec) -> std::result::Result<String, String> {
+    let is_number = matches!(value, DataTypes::Int(_) | DataTypes::Float(_));
+
+    let text = match (spec.kind, value, spec.precision) {
+        (Kind::Display, DataTypes::Float(value), Some(precision)) => {
+            format!("{:.*}", precision, value)
+        }
+        (Kind::Display, DataTypes::Str(value), Some(precision)) => {
+            value.chars().take(precision).collect()
+        }
+        (_, value, Some(_)) => {
+            return Err(format!(
+                "Precision only works on floats and strings, not a {}",
+                value.type_name()
+            ))
+        }
+        (Kind::Display, value, None) => value.to_string(),
+        (Kind::Hex, DataTypes::Int(value), None) => format!("{:x}", value),
+        (Kind::UpperHex, DataTypes::Int(value), None) => format!("{:X}", value),
+        (Kind::Binary, DataTypes::Int(value), None) => format!("{:b}", value),
+        (Kind::Octal, DataTypes::Int(value), None) => format!("{:o}", value),
+        (_, value, None) => {
+            return Err(format!(
+                "Hex, binary and octal only work on ints, not a {}",
+                value.type_name()
+            ))
+        }
+    };
+
+    let negative = text.starts_with('-');
+    let text = match spec.sign {
+        true if !is_number => {
+            return Err(format!(
+                "+ only works on numbers, not a {}",
+                value.type_name()
+            ))
+        }
+        true if !negative => format!("+{}", text),
+        _ => text,
+    };
+
+    let length = text.chars().count();
+    if length >= spec.width {
+        return Ok(text);
+    }
+    let padding = spec.width - length;
+
+    // zeros go between the sign and the digits, like -0005
+    if spec.zero && is_number {
+        let (sign, digits) = match text.strip_prefix(['-', '+']) {
+            Some(digits) => text.split_at(text.len() - digits.len()),
+            None => ("", text.as_str()),
+        };
+        return Ok(format!("{}{}{}", sign, "0".repeat(padding), digits));
+    }
+
+    let fill = |count: usize| spec.fill.to_string().repeat(count);
+    let align = spec
+        .align
+        .unwrap_or(if is_number { Align::Right } else { Align::Left });
+
+    Ok(match align {
+        Align::Left => format!("{}{}", text, fill(padding)),
+        Align::Right => format!("{}{}", fill(padding), text),
+        Align::Center => format!(
+            "{}{}{}",
+            fill(padding / 2),
+            text,
+            fill(padding - padding / 2)
+        ),
+    })
+}
@@ -0,0 +1,85 @@
+use std::io::Write;
+use std::path::Path;
+
+use super::super::{Error, Result};
+use super::expect_str;
+use crate::processing::permissions::Permissions;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Runs the builtins of the `fs` namespace, each checking the permissions for its path first
+pub(super) fn call(
+    builtin: Builtin,
+    pos: &Position,
+    args: &[DataTypes],
+    permissions: &Permissions,
+) -> Result<Option<DataTypes>> {
+    let path = Path::new(expect_str(&args[0], pos)?);
+    let failed = |e: std::io::Error| {
+        Error::FileSystem(path.display().to_string(), e.to_string(), pos.clone())
+    };
+
+    match builtin {
+        Builtin::FsRead => {
+            check_read(path, permissions, pos)?;
+            let contents = std::fs::read_to_string(path).map_err(failed)?;
+            Ok(Some(DataTypes::Str(contents)))
+        }
+        Builtin::FsWrite => {
+            check_write(path, permissions, pos)?;
+            std::fs::write(path, args[1].to_string()).map_err(failed)?;
+            Ok(None)
+        }
+        Builtin::FsAppend => {
+            check_write(path, permissions, pos)?;
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(failed)?;
+            file.write_all(args[1].to_string().as_bytes())
+                .map_err(failed)?;
+            Ok(None)
+        }
+        Builtin::FsExists => {
+            check_read(path, permissions, pos)?;
+            Ok(Some(DataTypes::Bool(path.exists())))
+        }
+        // names of what is in the directory, sorted so scripts see the same order everywhere
+        Builtin::FsListDir => {
+            check_read(path, permissions, pos)?;
+            let mut names = Vec::new();
+            for entry in std::fs::read_dir(path).map_err(failed)? {
+                let entry = entry.map_err(failed)?;
+                names.push(entry.file_name().to_string_lossy().to_string());
+            }
+            names.sort();
+
+            Ok(Some(DataTypes::Array(
+                names.into_iter().map(DataTypes::Str).collect(),
+            )))
+        }
+        other => unreachable!("{} is not part of fs", other),
+    }
+}
+
+fn check_read(path: &Path, permissions: &Permissions, pos: &Position) -> Result<()> {
+    match permissions.can_read(path) {
+        true => Ok(()),
+        false => Err(Error::PermissionDenied(
+            "read",
+            path.display().to_string(),
+            pos.clone(),
+        )),
+    }
+}
+
+fn check_write(path: &Path, permissions: &Permissions, pos: &Position) -> Result<()> {
+    match permissions.can_write(path) {
+        true => Ok(()),
+        false => Err(Error::PermissionDenied(
+            "write",
+            path.display().to_string(),
+            pos.clone(),
+        )),
+    }
+}
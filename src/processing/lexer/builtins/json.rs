@@ -0,0 +1,412 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::super::{Error, Result};
+use super::expect_str;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Deepest arrays and objects can be nested, so a hostile document can't overflow the stack
+const MAX_DEPTH: usize = 128;
+
+/// Most spaces `json.stringify` indents each level by, so a typo can't ask for gigabytes of spaces
+const MAX_INDENT: usize = 16;
+
+/// Runs the builtins of the `json` namespace
+pub(super) fn call(builtin: Builtin, pos: &Position, args: &[DataTypes]) -> Result<DataTypes> {
+    match builtin {
+        Builtin::JsonParse => {
+            let text = expect_str(&args[0], pos)?;
+            let mut reader = Reader::new(text);
+            let value = reader.document().map_err(|message| {
+                Error::InvalidJson(message, reader.line, reader.column, pos.clone())
+            })?;
+            Ok(value)
+        }
+        Builtin::JsonStringify => {
+            // pretty can be a bool or how many spaces to indent by
+            let indent = match args.get(1) {
+                None | Some(DataTypes::Bool(false)) => 0,
+                Some(DataTypes::Bool(true)) => 2,
+                Some(DataTypes::Int(spaces)) if *spaces > MAX_INDENT as i32 => {
+                    return Err(Error::IndentTooBig(*spaces, MAX_INDENT, pos.clone()))
+                }
+                Some(DataTypes::Int(spaces)) if *spaces >= 0 => *spaces as usize,
+                Some(other) => {
+                    let expected = "bool or a non-negative int";
+                    return Err(Error::UnexpectedType(expected, other.clone(), pos.clone()));
+                }
+            };
+
+            let mut json = String::new();
+            write_value(&mut json, &args[0], indent, 0, pos)?;
+            Ok(DataTypes::Str(json))
+        }
+        other => unreachable!("{} is not part of json", other),
+    }
+}
+
+/// Reads JSON text into `DataTypes`, keeping track of where it is for errors
+///
+/// Numbers that fit in an int become one, the rest become floats, objects become maps that keep their key order
+struct Reader<'t> {
+    chars: Peekable<Chars<'t>>,
+    /// Line and column of the next char, starting at 1 like an editor shows them
+    line: usize,
+    column: usize,
+    depth: usize,
+}
+
+type Read<T> = std::result::Result<T, String>;
+
+impl<'t> Reader<'t> {
+    fn new(text: &'t str) -> Reader<'t> {
+        Reader {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            depth: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Read<()> {
+        match self.chars.peek() {
+            Some(c) if *c == expected => {
+                self.next();
+                Ok(())
+            }
+            Some(c) => Err(format!("Expected {:?} but found {:?}", expected, c)),
+            None => Err(format!("Expected {:?} but the text ended", expected)),
+        }
+    }
+
+    /// A single value with nothing but whitespace around it
+    fn document(&mut self) -> Read<DataTypes> {
+        let value = self.value()?;
+        self.skip_whitespace();
+
+        match self.chars.peek() {
+            None => Ok(value),
+            Some(c) => Err(format!("Unexpected {:?} after the value", c)),
+        }
+    }
+
+    fn value(&mut self) -> Read<DataTypes> {
+        self.skip_whitespace();
+
+        match self.chars.peek() {
+            Some('{') => self.nested(Reader::object),
+            Some('[') => self.nested(Reader::array),
+            Some('"') => self.string().map(DataTypes::Str),
+            Some('-' | '0'..='9') => self.number(),
+            Some('t') => self.word("true", DataTypes::Bool(true)),
+            Some('f') => self.word("false", DataTypes::Bool(false)),
+            Some('n') => self.word("null", DataTypes::None),
+            Some(c) => Err(format!("Unexpected {:?}", c)),
+            None => Err("Expected a value but the text ended".to_string()),
+        }
+    }
+
+    fn nested(&mut self, read: fn(&mut Reader<'t>) -> Read<DataTypes>) -> Read<DataTypes> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("Nested deeper than {}", MAX_DEPTH));
+        }
+
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Read<DataTypes> {
+        self.expect('{')?;
+        let mut entries: Vec<(String, DataTypes)> = Vec::new();
+
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'}') {
+            self.next();
+            return Ok(DataTypes::Map(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.chars.peek() != Some(&'"') {
+                return Err("Expected a string key".to_string());
+            }
+            let key = self.string()?;
+
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value()?;
+
+            // the last one wins, like most JSON readers
+            match entries.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(entry) => entry.1 = value,
+                None => entries.push((key, value)),
+            }
+
+            if self.separator('}')? {
+                return Ok(DataTypes::Map(entries));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Read<DataTypes> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&']') {
+            self.next();
+            return Ok(DataTypes::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            if self.separator(']')? {
+                return Ok(DataTypes::Array(values));
+            }
+        }
+    }
+
+    /// Reads the `,` between values or the `close` that ends them, true once it is closed
+    fn separator(&mut self, close: char) -> Read<bool> {
+        self.skip_whitespace();
+
+        let closed = match self.chars.peek() {
+            Some(',') => false,
+            Some(c) if *c == close => true,
+            Some(c) => return Err(format!("Expected ',' or '{}' but found {:?}", close, c)),
+            None => return Err(format!("Expected ',' or '{}' but the text ended", close)),
+        };
+
+        self.next();
+        Ok(closed)
+    }
+
+    fn string(&mut self) -> Read<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => string.push(self.escape()?),
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(format!("Control character {:?} has to be escaped", c))
+                }
+                Some(c) => string.push(c),
+                None => return Err("Unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Read<char> {
+        match self.next() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('/') => Ok('/'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => {
+                let high = self.hex()?;
+                if !(0xD800..0xDC00).contains(&high) {
+                    return char::from_u32(high)
+                        .ok_or_else(|| format!("Invalid escape \\u{:04x}", high));
+                }
+
+                // characters outside the basic plane are written as a pair of escapes
+                self.expect('\\')?;
+                self.expect('u')?;
+                let low = self.hex()?;
+                if !(0xDC00..0xE000).contains(&low) {
+                    return Err(format!("Invalid escape \\u{:04x}\\u{:04x}", high, low));
+                }
+
+                let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                char::from_u32(code)
+                    .ok_or_else(|| format!("Invalid escape \\u{:04x}\\u{:04x}", high, low))
+            }
+            Some(c) => Err(format!("Invalid escape \\{}", c)),
+            None => Err("Unterminated string".to_string()),
+        }
+    }
+
+    fn hex(&mut self) -> Read<u32> {
+        let mut hex = String::new();
+        for _ in 0..4 {
+            match self.next() {
+                Some(c) if c.is_ascii_hexdigit() => hex.push(c),
+                _ => return Err("Expected 4 hex digits after \\u".to_string()),
+            }
+        }
+
+        Ok(u32::from_str_radix(&hex, 16).unwrap())
+    }
+
+    fn number(&mut self) -> Read<DataTypes> {
+        let mut number = String::new();
+        while let Some(c) = self.chars.peek() {
+            if !matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9') {
+                break;
+            }
+            number.push(*c);
+            self.next();
+        }
+
+        if !is_json_number(&number) {
+            return Err(format!("Invalid number {}", number));
+        }
+
+        let is_integer = !number.contains(['.', 'e', 'E']);
+        match (is_integer, number.parse::<i32>(), number.parse::<f64>()) {
+            (true, Ok(int), _) => Ok(DataTypes::Int(int)),
+            (_, _, Ok(float)) if float.is_finite() => Ok(DataTypes::Float(float)),
+            _ => Err(format!("Invalid number {}", number)),
+        }
+    }
+
+    fn word(&mut self, word: &str, value: DataTypes) -> Read<DataTypes> {
+        for expected in word.chars() {
+            if self.chars.peek() != Some(&expected) {
+                return Err(format!("Expected {}", word));
+            }
+            self.next();
+        }
+
+        Ok(value)
+    }
+}
+
+/// Whether the text follows the JSON grammar for numbers, which is stricter than what `f64` parses
+///
+/// EXAMPLE:
+///     "-0.5e+3" -> true
+///     "01", "1.", ".5", "1e" -> false
+fn is_json_number(number: &str) -> bool {
+    // how many digits the text starts with
+    let digits = |text: &str| text.chars().take_while(char::is_ascii_digit).count();
+
+    let rest = number.strip_prefix('-').unwrap_or(number);
+    let rest = match digits(rest) {
+        0 => return false,
+        n if n > 1 && rest.starts_with('0') => return false,
+        n => &rest[n..],
+    };
+
+    let rest = match rest.strip_prefix('.') {
+        Some(fraction) => match digits(fraction) {
+            0 => return false,
+            n => &fraction[n..],
+        },
+        None => rest,
+    };
+
+    match rest.strip_prefix(['e', 'E']) {
+        Some(exponent) => {
+            let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            let n = digits(exponent);
+            n > 0 && n == exponent.len()
+        }
+        None => rest.is_empty(),
+    }
+}
+
+/// Writes the value as JSON, on one line when `indent` is 0 and spread over lines indented by `indent` otherwise
+fn write_value(
+    json: &mut String,
+    value: &DataTypes,
+    indent: usize,
+    depth: usize,
+    pos: &Position,
+) -> Result<()> {
+    let new_line = |json: &mut String, depth: usize| {
+        if indent > 0 {
+            json.push('\n');
+            json.push_str(&" ".repeat(indent * depth));
+        }
+    };
+
+    match value {
+        DataTypes::None => json.push_str("null"),
+        DataTypes::Bool(value) => json.push_str(&value.to_string()),
+        DataTypes::Int(value) => json.push_str(&value.to_string()),
+        // JSON has no way to write these
+        DataTypes::Float(float) if !float.is_finite() => {
+            return Err(Error::OutOfDomain(
+                Builtin::JsonStringify,
+                value.clone(),
+                pos.clone(),
+            ))
+        }
+        DataTypes::Float(_) => json.push_str(&value.to_string()),
+        DataTypes::Str(value) => write_string(json, value),
+        DataTypes::Char(value) => write_string(json, &value.to_string()),
+        DataTypes::Array(values) if values.is_empty() => json.push_str("[]"),
+        DataTypes::Array(values) => {
+            json.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                new_line(json, depth + 1);
+                write_value(json, value, indent, depth + 1, pos)?;
+            }
+            new_line(json, depth);
+            json.push(']');
+        }
+        DataTypes::Map(entries) if entries.is_empty() => json.push_str("{}"),
+        DataTypes::Map(entries) => {
+            json.push('{');
+            for (index, (key, value)) in entries.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                new_line(json, depth + 1);
+                write_string(json, key);
+                json.push_str(if indent > 0 { ": " } else { ":" });
+                write_value(json, value, indent, depth + 1, pos)?;
+            }
+            new_line(json, depth);
+            json.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+fn write_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
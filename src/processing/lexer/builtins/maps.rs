@@ -0,0 +1,28 @@
+use super::super::{Error, Result};
+use super::expect_str;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Runs the builtins of the `map` namespace
+pub(super) fn call(builtin: Builtin, pos: &Position, args: &[DataTypes]) -> Result<DataTypes> {
+    let entries = match &args[0] {
+        DataTypes::Map(entries) => entries,
+        other => return Err(Error::UnexpectedType("map", other.clone(), pos.clone())),
+    };
+
+    match builtin {
+        Builtin::MapGet => {
+            let key = expect_str(&args[1], pos)?;
+            match entries.iter().find(|(existing, _)| existing == key) {
+                Some((_, value)) => Ok(value.clone()),
+                None => Err(Error::MissingKey(key.to_string(), pos.clone())),
+            }
+        }
+        Builtin::MapKeys => Ok(DataTypes::Array(
+            entries
+                .iter()
+                .map(|(key, _)| DataTypes::Str(key.to_string()))
+                .collect(),
+        )),
+        other => unreachable!("{} is not part of map", other),
+    }
+}
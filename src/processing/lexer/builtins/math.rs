@@ -0,0 +1,99 @@
+use super::super::{Error, Result};
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Runs the builtins of the `math` namespace
+///
+/// Ints stay ints where the result can be one, like `math.abs` or `math.max`, everything else gives back a float
+pub(super) fn call(builtin: Builtin, pos: &Position, args: &[DataTypes]) -> Result<DataTypes> {
+    match builtin {
+        Builtin::MathPi => Ok(DataTypes::Float(std::f64::consts::PI)),
+        Builtin::MathE => Ok(DataTypes::Float(std::f64::consts::E)),
+        Builtin::MathAbs => match &args[0] {
+            DataTypes::Int(value) => match value.checked_abs() {
+                Some(value) => Ok(DataTypes::Int(value)),
+                None => Err(Error::Overflow(builtin, pos.clone())),
+            },
+            other => Ok(DataTypes::Float(number(other, pos)?.abs())),
+        },
+        Builtin::MathMin | Builtin::MathMax => {
+            let pick_min = builtin == Builtin::MathMin;
+            match (&args[0], &args[1]) {
+                (DataTypes::Int(a), DataTypes::Int(b)) if pick_min => Ok(DataTypes::Int(*a.min(b))),
+                (DataTypes::Int(a), DataTypes::Int(b)) => Ok(DataTypes::Int(*a.max(b))),
+                (a, b) if pick_min => Ok(DataTypes::Float(number(a, pos)?.min(number(b, pos)?))),
+                (a, b) => Ok(DataTypes::Float(number(a, pos)?.max(number(b, pos)?))),
+            }
+        }
+        Builtin::MathPow => match (&args[0], &args[1]) {
+            (DataTypes::Int(base), DataTypes::Int(exponent)) if *exponent >= 0 => {
+                match base.checked_pow(*exponent as u32) {
+                    Some(value) => Ok(DataTypes::Int(value)),
+                    None => Err(Error::Overflow(builtin, pos.clone())),
+                }
+            }
+            (base, exponent) => {
+                let value = number(base, pos)?.powf(number(exponent, pos)?);
+                if !value.is_finite() {
+                    let args = DataTypes::Array(vec![base.clone(), exponent.clone()]);
+                    return Err(Error::OutOfDomain(builtin, args, pos.clone()));
+                }
+
+                Ok(DataTypes::Float(value))
+            }
+        },
+        Builtin::MathFloor => to_int(builtin, number(&args[0], pos)?.floor(), pos),
+        Builtin::MathCeil => to_int(builtin, number(&args[0], pos)?.ceil(), pos),
+        Builtin::MathRound => to_int(builtin, number(&args[0], pos)?.round(), pos),
+        Builtin::MathSqrt => float_in_domain(builtin, &args[0], pos, |x| x >= 0.0, f64::sqrt),
+        Builtin::MathSin => float_in_domain(builtin, &args[0], pos, |_| true, f64::sin),
+        Builtin::MathCos => float_in_domain(builtin, &args[0], pos, |_| true, f64::cos),
+        Builtin::MathTan => float_in_domain(builtin, &args[0], pos, |_| true, f64::tan),
+        Builtin::MathAsin => float_in_domain(builtin, &args[0], pos, |x| x.abs() <= 1.0, f64::asin),
+        Builtin::MathAcos => float_in_domain(builtin, &args[0], pos, |x| x.abs() <= 1.0, f64::acos),
+        Builtin::MathAtan => float_in_domain(builtin, &args[0], pos, |_| true, f64::atan),
+        Builtin::MathLog => float_in_domain(builtin, &args[0], pos, |x| x > 0.0, f64::ln),
+        Builtin::MathLog10 => float_in_domain(builtin, &args[0], pos, |x| x > 0.0, f64::log10),
+        Builtin::MathLog2 => float_in_domain(builtin, &args[0], pos, |x| x > 0.0, f64::log2),
+        Builtin::MathExp => float_in_domain(builtin, &args[0], pos, |_| true, f64::exp),
+        other => unreachable!("{} is not part of math", other),
+    }
+}
+
+/// Reads an int or a float as a float
+fn number(value: &DataTypes, pos: &Position) -> Result<f64> {
+    match value {
+        DataTypes::Int(value) => Ok(*value as f64),
+        DataTypes::Float(value) => Ok(*value),
+        other => Err(Error::UnexpectedType("number", other.clone(), pos.clone())),
+    }
+}
+
+fn to_int(builtin: Builtin, value: f64, pos: &Position) -> Result<DataTypes> {
+    if value.is_nan() || value < i32::MIN as f64 || value > i32::MAX as f64 {
+        return Err(Error::Overflow(builtin, pos.clone()));
+    }
+
+    Ok(DataTypes::Int(value as i32))
+}
+
+/// Applies `function` to the argument as a float, as long as `in_domain` holds for it and the result is neither NaN
+/// nor infinite
+fn float_in_domain(
+    builtin: Builtin,
+    value: &DataTypes,
+    pos: &Position,
+    in_domain: impl Fn(f64) -> bool,
+    function: impl Fn(f64) -> f64,
+) -> Result<DataTypes> {
+    let x = number(value, pos)?;
+    if !in_domain(x) {
+        return Err(Error::OutOfDomain(builtin, value.clone(), pos.clone()));
+    }
+
+    let result = function(x);
+    if !result.is_finite() {
+        return Err(Error::OutOfDomain(builtin, value.clone(), pos.clone()));
+    }
+
+    Ok(DataTypes::Float(result))
+}
@@ -0,0 +1,316 @@
+use super::values::{evaluate, interpolate, unescape};
+use super::Token;
+use super::{BaseLexing, BaseLexingReturn, Error, Result};
+use crate::processing::io::{InputSource, OutputSink};
+use crate::processing::permissions::Permissions;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position, Variable};
+use crate::processing::types::Line;
+use crate::processing::utils::random::Rng;
+use crate::processing::utils::variables::declare_variable;
+
+mod arrays;
+mod csv;
+mod env;
+mod format;
+mod fs;
+mod json;
+mod maps;
+mod math;
+mod random;
+mod strings;
+
+/// Branch of the lexer for the functions built into the language, like `print`
+///
+/// A builtin is followed by its arguments, each being a value, an `a op b` or another builtin with its own arguments
+///
+/// EXAMPLE:
+///     "println math.max x + 1 10"
+///     println(math.max(x + 1, 10))
+pub struct Builtins<'a> {
+    variables: Vec<Variable>,
+    output: &'a mut dyn OutputSink,
+    input: &'a mut dyn InputSource,
+    permissions: &'a Permissions,
+    args: &'a [String],
+    rng: &'a mut Rng,
+    /// Set once `exit` ran
+    exit_code: Option<i32>,
+}
+
+impl<'a> Builtins<'a> {
+    pub fn new(
+        variables: &[Variable],
+        output: &'a mut dyn OutputSink,
+        input: &'a mut dyn InputSource,
+        permissions: &'a Permissions,
+        args: &'a [String],
+        rng: &'a mut Rng,
+    ) -> Builtins<'a> {
+        Builtins {
+            variables: variables.to_vec(),
+            output,
+            input,
+            permissions,
+            args,
+            rng,
+            exit_code: None,
+        }
+    }
+
+    /// Works out the value of all the tokens, `None` if they are a builtin without a value like `print`
+    fn evaluate(&mut self, tokens: &[Token], line: &Line) -> Result<Option<DataTypes>> {
+        let (value, used) = self.evaluate_operand(tokens, line)?;
+
+        match tokens.get(used) {
+            Some(extra) => Err(Error::UnexpectedArgument(extra.clone(), extra.get_pos())),
+            None => Ok(value),
+        }
+    }
+
+    /// Works out the value at the start of the tokens, giving back how many tokens it used
+    ///
+    /// EXAMPLE:
+    ///     ["x", "+", "1", "10"] -> (x + 1, 3)
+    ///     ["math.abs", "x", "10"] -> (math.abs(x), 2)
+    fn evaluate_operand(
+        &mut self,
+        tokens: &[Token],
+        line: &Line,
+    ) -> Result<(Option<DataTypes>, usize)> {
+        match tokens {
+            [] => Err(Error::UnexpectedEndOfLine(line.number)),
+            [Token::Builtin(builtin, pos), ..] => {
+                let (min_args, max_args) = builtin.arguments();
+                let mut args = Vec::new();
+                let mut used = 1;
+
+                while args.len() < max_args {
+                    let rest = &tokens[used..];
+                    if rest.is_empty() && args.len() >= min_args {
+                        break;
+                    }
+                    if rest.is_empty() {
+                        return Err(Error::ExpectedArguments(*builtin, min_args, pos.clone()));
+                    }
+
+                    let (value, arg_used) = match (builtin, &rest[0]) {
+                        // checked before running it, so its output or exit doesn't happen first
+                        (_, Token::Builtin(inner, inner_pos)) if !inner.has_value() => {
+                            return Err(Error::BuiltinHasNoValue(*inner, inner_pos.clone()))
+                        }
+                        // the template of format keeps its {} rather than being interpolated
+                        (Builtin::Format, Token::Str(text, str_pos)) if args.is_empty() => {
+                            (Some(DataTypes::Str(unescape(text, str_pos)?)), 1)
+                        }
+                        _ => self.evaluate_operand(rest, line)?,
+                    };
+                    let value = match (value, &rest[0]) {
+                        (Some(value), _) => value,
+                        (None, Token::Builtin(inner, inner_pos)) => {
+                            return Err(Error::BuiltinHasNoValue(*inner, inner_pos.clone()))
+                        }
+                        (None, other) => {
+                            return Err(Error::ExpectedValue(other.clone(), other.get_pos()))
+                        }
+                    };
+
+                    args.push(value);
+                    used += arg_used;
+                }
+
+                Ok((self.call(*builtin, pos, args)?, used))
+            }
+            [_, operator, _, ..] if is_operator(operator) => {
+                let value = evaluate(&tokens[..3], line, &self.variables)?;
+                Ok((Some(value), 3))
+            }
+            [Token::Str(text, pos), ..] => {
+                let text = self.interpolate(text, pos, line)?;
+                Ok((Some(DataTypes::Str(text)), 1))
+            }
+            [_, ..] => {
+                let value = evaluate(&tokens[..1], line, &self.variables)?;
+                Ok((Some(value), 1))
+            }
+        }
+    }
+
+    /// Fills in the `{expression}` parts of the string, each worked out like a line would be: by the builtins, then
+    /// as a value and then as math
+    ///
+    /// EXAMPLE:
+    ///     "abs = {math.abs x}" with x = -3
+    ///     "abs = 3"
+    fn interpolate(&mut self, text: &str, pos: &Position, line: &Line) -> Result<String> {
+        let variables = self.variables.clone();
+        let mut evaluate_expression = |tokens: &[Token]| match tokens {
+            // checked before running it, so `{exit 3}` doesn't exit before failing
+            [Token::Builtin(builtin, builtin_pos), ..] if !builtin.has_value() => {
+                Err(Error::BuiltinHasNoValue(*builtin, builtin_pos.clone()))
+            }
+            [Token::Builtin(builtin, builtin_pos), ..] => match self.evaluate(tokens, line)? {
+                Some(value) => Ok(value),
+                None => Err(Error::BuiltinHasNoValue(*builtin, builtin_pos.clone())),
+            },
+            _ => evaluate(tokens, line, &variables),
+        };
+
+        interpolate(text, pos, line, &variables, &mut evaluate_expression)
+    }
+
+    /// Runs the builtin with its already worked out arguments, giving back its value if it has one
+    fn call(
+        &mut self,
+        builtin: Builtin,
+        pos: &Position,
+        args: Vec<DataTypes>,
+    ) -> Result<Option<DataTypes>> {
+        match builtin {
+            Builtin::Print => self.print(&args[0], false),
+            Builtin::PrintLine => match args.first() {
+                Some(value) => self.print(value, true),
+                None => {
+                    self.output.write("\n");
+                    Ok(None)
+                }
+            },
+            Builtin::Input => {
+                if let Some(prompt) = args.first() {
+                    self.print(prompt, false)?;
+                }
+                self.read_line(pos).map(Some)
+            }
+            Builtin::ReadLine => self.read_line(pos).map(Some),
+            Builtin::ReadAll => match self.input.read_all() {
+                Ok(contents) => Ok(Some(DataTypes::Str(contents))),
+                Err(e) => Err(Error::InputFailed(e.to_string(), pos.clone())),
+            },
+            Builtin::ParseInt => {
+                let text = expect_str(&args[0], pos)?;
+                match text.trim().parse::<i32>() {
+                    Ok(value) => Ok(Some(DataTypes::Int(value))),
+                    Err(_) => Err(Error::InvalidInt(text.to_string(), pos.clone())),
+                }
+            }
+            Builtin::ParseFloat => {
+                let text = expect_str(&args[0], pos)?;
+                match text.trim().parse::<f64>() {
+                    Ok(value) => Ok(Some(DataTypes::Float(value))),
+                    Err(_) => Err(Error::InvalidFloat(text.to_string(), pos.clone())),
+                }
+            }
+            Builtin::Format => {
+                let template = expect_str(&args[0], pos)?;
+                let formatted = format::format(template, &args[1..], &self.variables, pos)?;
+                Ok(Some(DataTypes::Str(formatted)))
+            }
+            Builtin::Exit => {
+                if !self.permissions.exit {
+                    return Err(Error::NotAllowed("exit", "--allow-exit", pos.clone()));
+                }
+
+                let code = match args.first() {
+                    None => 0,
+                    Some(DataTypes::Int(code)) if (0..=255).contains(code) => *code,
+                    Some(DataTypes::Int(_)) => {
+                        return Err(Error::OutOfDomain(builtin, args[0].clone(), pos.clone()))
+                    }
+                    Some(other) => {
+                        return Err(Error::UnexpectedType("int", other.clone(), pos.clone()))
+                    }
+                };
+
+                self.exit_code = Some(code);
+                Ok(None)
+            }
+            _ if builtin.name().starts_with("env.") => {
+                env::call(builtin, pos, &args, self.permissions, self.args).map(Some)
+            }
+            _ if builtin.name().starts_with("random.") => {
+                random::call(builtin, pos, &args, self.rng).map(Some)
+            }
+            _ if builtin.name().starts_with("csv.") => csv::call(builtin, pos, &args).map(Some),
+            _ if builtin.name().starts_with("json.") => json::call(builtin, pos, &args).map(Some),
+            _ if builtin.name().starts_with("map.") => maps::call(builtin, pos, &args).map(Some),
+            _ if builtin.name().starts_with("fs.") => {
+                fs::call(builtin, pos, &args, self.permissions)
+            }
+            _ if builtin.name().starts_with("array.") => {
+                arrays::call(builtin, pos, &args).map(Some)
+            }
+            _ if builtin.name().starts_with("str.") => strings::call(builtin, pos, &args).map(Some),
+            _ => math::call(builtin, pos, &args).map(Some),
+        }
+    }
+
+    fn print(&mut self, value: &DataTypes, new_line: bool) -> Result<Option<DataTypes>> {
+        let mut text = value.to_string();
+        if new_line {
+            text.push('\n');
+        }
+
+        self.output.write(&text);
+        Ok(None)
+    }
+
+    fn read_line(&mut self, pos: &Position) -> Result<DataTypes> {
+        match self.input.read_line() {
+            Ok(Some(line)) => Ok(DataTypes::Str(line)),
+            Ok(None) => Err(Error::EndOfInput(pos.clone())),
+            Err(e) => Err(Error::InputFailed(e.to_string(), pos.clone())),
+        }
+    }
+}
+
+impl BaseLexing for Builtins<'_> {
+    fn is_valid_line(&self, line: &Line) -> Result<()> {
+        match self.statement(line)?.as_slice() {
+            [Token::Builtin(_, _), ..] => Ok(()),
+            // a string is worked out here rather than in values, its {} can call builtins
+            [Token::Str(_, _)] => Ok(()),
+            [other, ..] => Err(Error::ExpectedBuiltin(other.clone(), other.get_pos())),
+            [] => Err(Error::UnexpectedEndOfLine(line.number)),
+        }
+    }
+
+    fn execute(&mut self, line: &Line) -> Result<BaseLexingReturn> {
+        let statement = self.statement(line)?;
+        let value = self.evaluate(&statement, line)?;
+
+        if let Some(code) = self.exit_code {
+            return Ok(BaseLexingReturn::Exit(code));
+        }
+
+        if !self.is_assigning_to_variable(line) {
+            return match value {
+                Some(value) => Ok(BaseLexingReturn::Value(value)),
+                None => Ok(BaseLexingReturn::Empty),
+            };
+        }
+
+        let value = match (value, &statement[0]) {
+            (Some(value), _) => value,
+            (None, Token::Builtin(builtin, pos)) => {
+                return Err(Error::BuiltinHasNoValue(*builtin, pos.clone()))
+            }
+            (None, other) => return Err(Error::ExpectedBuiltin(other.clone(), other.get_pos())),
+        };
+
+        let variable = declare_variable(line, &self.variables, value)?;
+        Ok(BaseLexingReturn::Variable(variable))
+    }
+}
+
+fn is_operator(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Add(_) | Token::Subtract(_) | Token::Divide(_) | Token::Multiply(_)
+    )
+}
+
+fn expect_str<'v>(value: &'v DataTypes, pos: &Position) -> Result<&'v str> {
+    match value {
+        DataTypes::Str(text) => Ok(text),
+        other => Err(Error::UnexpectedType("str", other.clone(), pos.clone())),
+    }
+}
@@ -0,0 +1,46 @@
+use super::super::{Error, Result};
+use super::arrays::expect_array;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+use crate::processing::utils::random::Rng;
+
+/// Runs the builtins of the `random` namespace
+pub(super) fn call(
+    builtin: Builtin,
+    pos: &Position,
+    args: &[DataTypes],
+    rng: &mut Rng,
+) -> Result<DataTypes> {
+    match builtin {
+        // both ends can come out
+        Builtin::RandomInt => {
+            let (low, high) = match (&args[0], &args[1]) {
+                (DataTypes::Int(low), DataTypes::Int(high)) => (*low, *high),
+                (DataTypes::Int(_), other) | (other, _) => {
+                    return Err(Error::UnexpectedType("int", other.clone(), pos.clone()))
+                }
+            };
+            if low > high {
+                return Err(Error::InvalidRange(low, high, pos.clone()));
+            }
+
+            let size = (high as i64 - low as i64 + 1) as u64;
+            Ok(DataTypes::Int((low as i64 + rng.below(size) as i64) as i32))
+        }
+        Builtin::RandomFloat => Ok(DataTypes::Float(rng.next_float())),
+        Builtin::RandomChoice => {
+            let values = expect_array(&args[0], pos)?;
+            if values.is_empty() {
+                return Err(Error::EmptyArray(builtin, pos.clone()));
+            }
+
+            Ok(values[rng.below(values.len() as u64) as usize].clone())
+        }
+        // gives back a shuffled copy, like the array builtins
+        Builtin::RandomShuffle => {
+            let mut values = expect_array(&args[0], pos)?.to_vec();
+            rng.shuffle(&mut values);
+            Ok(DataTypes::Array(values))
+        }
+        other => unreachable!("{} is not part of random", other),
+    }
+}
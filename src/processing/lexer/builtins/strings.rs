@@ -0,0 +1,108 @@
+use super::super::{Error, Result};
+use super::expect_str;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+
+/// Runs the builtins of the `str` namespace
+///
+/// Lengths and indexes count chars rather than bytes, so "héllo" has a length of 5
+pub(super) fn call(builtin: Builtin, pos: &Position, args: &[DataTypes]) -> Result<DataTypes> {
+    match builtin {
+        Builtin::StrJoin => join(&args[0], expect_str(&args[1], pos)?, pos),
+        _ => call_on_str(builtin, expect_str(&args[0], pos)?, pos, &args[1..]),
+    }
+}
+
+/// Runs the builtins that take a string as their first argument
+fn call_on_str(
+    builtin: Builtin,
+    text: &str,
+    pos: &Position,
+    args: &[DataTypes],
+) -> Result<DataTypes> {
+    match builtin {
+        Builtin::StrLen => length(builtin, text.chars().count(), pos),
+        Builtin::StrUpper => Ok(DataTypes::Str(text.to_uppercase())),
+        Builtin::StrLower => Ok(DataTypes::Str(text.to_lowercase())),
+        Builtin::StrTrim => Ok(DataTypes::Str(text.trim().to_string())),
+        Builtin::StrSplit => {
+            let separator = expect_str(&args[0], pos)?;
+            if separator.is_empty() {
+                return Err(Error::EmptySeparator(pos.clone()));
+            }
+
+            let parts = text
+                .split(separator)
+                .map(|part| DataTypes::Str(part.to_string()))
+                .collect();
+            Ok(DataTypes::Array(parts))
+        }
+        Builtin::StrContains => Ok(DataTypes::Bool(text.contains(expect_str(&args[0], pos)?))),
+        Builtin::StrStartsWith => Ok(DataTypes::Bool(
+            text.starts_with(expect_str(&args[0], pos)?),
+        )),
+        Builtin::StrReplace => {
+            let from = expect_str(&args[0], pos)?;
+            let to = expect_str(&args[1], pos)?;
+            Ok(DataTypes::Str(text.replace(from, to)))
+        }
+        // -1 when it is not there
+        Builtin::StrFind => match text.find(expect_str(&args[0], pos)?) {
+            Some(byte_index) => length(builtin, text[..byte_index].chars().count(), pos),
+            None => Ok(DataTypes::Int(-1)),
+        },
+        Builtin::StrChars => Ok(DataTypes::Array(
+            text.chars().map(DataTypes::Char).collect(),
+        )),
+        Builtin::StrCharAt => {
+            let chars: Vec<char> = text.chars().collect();
+            let index = char_index(&args[0], chars.len(), false, pos)?;
+            Ok(DataTypes::Char(chars[index]))
+        }
+        // start is inclusive and end is exclusive, like "hello"[1..3] == "el"
+        Builtin::StrSubstring => {
+            let chars: Vec<char> = text.chars().collect();
+            let start = char_index(&args[0], chars.len(), true, pos)?;
+            let end = char_index(&args[1], chars.len(), true, pos)?;
+            if start > end {
+                return Err(Error::IndexOutOfRange(start as i32, end, pos.clone()));
+            }
+
+            Ok(DataTypes::Str(chars[start..end].iter().collect()))
+        }
+        other => unreachable!("{} is not part of str", other),
+    }
+}
+
+/// Joins the values of an array with the separator between them
+///
+/// EXAMPLE:
+///     ["a", "b", 3] ", " -> "a, b, 3"
+fn join(values: &DataTypes, separator: &str, pos: &Position) -> Result<DataTypes> {
+    let values = match values {
+        DataTypes::Array(values) => values,
+        other => return Err(Error::UnexpectedType("array", other.clone(), pos.clone())),
+    };
+
+    let parts: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    Ok(DataTypes::Str(parts.join(separator)))
+}
+
+fn length(builtin: Builtin, length: usize, pos: &Position) -> Result<DataTypes> {
+    match i32::try_from(length) {
+        Ok(length) => Ok(DataTypes::Int(length)),
+        Err(_) => Err(Error::Overflow(builtin, pos.clone())),
+    }
+}
+
+/// Reads an int as an index into `length` chars, `length` itself is only allowed as the end of a range
+fn char_index(value: &DataTypes, length: usize, is_end: bool, pos: &Position) -> Result<usize> {
+    let index = match value {
+        DataTypes::Int(index) => *index,
+        other => return Err(Error::UnexpectedType("int", other.clone(), pos.clone())),
+    };
+
+    match usize::try_from(index) {
+        Ok(checked) if checked < length || is_end && checked == length => Ok(checked),
+        _ => Err(Error::IndexOutOfRange(index, length, pos.clone())),
+    }
+}
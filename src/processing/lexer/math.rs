@@ -11,7 +11,7 @@ pub struct Math {
 }
 
 impl Math {
-    pub fn new(variables: &Vec<Variable>) -> Math {
+    pub fn new(variables: &[Variable]) -> Math {
         Math {
             variables: variables.to_vec(),
         }
@@ -35,6 +35,13 @@ impl Math {
         let num_1_pos = operator_pos - 1;
         let num_2_pos = operator_pos + 1;
 
+        // only ints can be added up, a float is named as such rather than being an operand in the wrong place
+        for pos in [num_1_pos, num_2_pos] {
+            if let Some((value, position)) = self.float_at_pos(line, pos) {
+                return Err(Error::UnexpectedType("int", value, position));
+            }
+        }
+
         let num_at_pos_1 = self.num_at_pos(line, num_1_pos);
         let num_at_pos_2 = self.num_at_pos(line, num_2_pos);
 
@@ -46,7 +53,10 @@ impl Math {
     }
 
     fn num_at_pos(&self, line: &Line, pos: usize) -> bool {
-        let token = line.tokenized_body[pos].clone();
+        let token = match line.tokenized_body.get(pos) {
+            Some(token) => token.clone(),
+            None => return false,
+        };
         if matches!(&token, Token::Int(_, _)) {
             return true;
         }
@@ -70,6 +80,22 @@ impl Math {
         true
     }
 
+    /// Gives back the float at the position, whether it is written out or the value of a variable
+    fn float_at_pos(&self, line: &Line, pos: usize) -> Option<(DataTypes, Position)> {
+        let value = match line.tokenized_body.get(pos)? {
+            Token::Float(value, _) => DataTypes::Float(*value),
+            Token::Variable(name, _) => self
+                .variables
+                .iter()
+                .find(|variable| variable.name == *name)
+                .map(|variable| variable.value.clone())
+                .filter(|value| matches!(value, DataTypes::Float(_)))?,
+            _ => return None,
+        };
+
+        Some((value, line.tokenized_body[pos].get_pos()))
+    }
+
     /// finds what position the operator is at
     fn find_operator(&self, line: &Line) -> Option<usize> {
         line.tokenized_body.iter().position(|token| {
@@ -83,7 +109,7 @@ impl Math {
     fn extract_int_value(
         &self,
         token: &Token,
-        variables: &Vec<Variable>,
+        variables: &[Variable],
         pos: Position,
     ) -> Result<i32> {
         match token {
@@ -119,10 +145,10 @@ impl BaseLexing for Math {
         self.rules(line)
     }
 
-    fn execute(&self, line: &Line) -> Result<BaseLexingReturn> {
+    fn execute(&mut self, line: &Line) -> Result<BaseLexingReturn> {
         let assign_to_var = self.is_assigning_to_variable(line);
         let final_line = if assign_to_var {
-            slice_variable_dec(line)
+            slice_variable_dec(line)?
         } else {
             line.tokenized_body.to_vec()
         };
@@ -136,11 +162,15 @@ impl BaseLexing for Math {
         let num_2 = self.extract_int_value(num_2_token, &self.variables, num_2_position)?;
 
         let operator = &final_line[1];
-        let value = match operator {
-            Token::Add(_) => BaseLexingReturn::Int(num_1 + num_2),
-            Token::Subtract(_) => BaseLexingReturn::Int(num_1 - num_2),
-            Token::Multiply(_) => BaseLexingReturn::Int(num_1 * num_2),
-            Token::Divide(_) => BaseLexingReturn::Int(num_1 / num_2),
+        let (result, symbol) = match operator {
+            Token::Add(_) => (num_1.checked_add(num_2), '+'),
+            Token::Subtract(_) => (num_1.checked_sub(num_2), '-'),
+            Token::Multiply(_) => (num_1.checked_mul(num_2), '*'),
+            Token::Divide(_) if num_2 == 0 => {
+                return Err(Error::DivisionByZero(operator.get_pos()))
+            }
+            // i32::MIN / -1 is the only division that doesn't fit
+            Token::Divide(_) => (num_1.checked_div(num_2), '/'),
             _ => {
                 return Err(Error::ExpectedOperator(
                     operator.clone(),
@@ -148,17 +178,28 @@ impl BaseLexing for Math {
                 ))
             }
         };
+        let value = match result {
+            Some(result) => BaseLexingReturn::Int(result),
+            None => {
+                return Err(Error::IntOverflow(
+                    num_1,
+                    symbol,
+                    num_2,
+                    operator.get_pos(),
+                ))
+            }
+        };
 
         if !assign_to_var {
             return Ok(value);
         }
 
-        let name = get_var_name(&line)?;
+        let name = get_var_name(line)?;
         let var_value = match value {
             BaseLexingReturn::Int(n) => n,
             _ => return Err(Error::FailedToInferType),
         };
-        let mutable = is_var_mutable(&line);
+        let mutable = is_var_mutable(line);
 
         if var_exists(&self.variables, &name) {
             return Err(Error::VariableAlreadyExists(name));
@@ -1,11 +1,16 @@
+use super::io::{InputSource, OutputSink, StdinSource, StdoutSink};
 use super::parser::Parser;
-use super::syntax_elements::{Position, Token, Variable};
+use super::permissions::Permissions;
+use super::syntax_elements::{Builtin, DataTypes, Position, Token, Variable};
 use super::types::{Line, LineNumber, LineTokenizedBody};
+use super::utils::random::Rng;
 use base::BaseLexing;
 use base::BaseLexingReturn;
 
 pub mod base;
+pub mod builtins;
 pub mod math;
+pub mod values;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -26,6 +31,12 @@ pub enum Error {
     #[error("Unexpected integer positions on line {0}")]
     UnexpectedIntegerPositions(LineNumber),
 
+    #[error("Division by zero at {0}")]
+    DivisionByZero(Position),
+
+    #[error("{0} {1} {2} is too big for an int at {3}")]
+    IntOverflow(i32, char, i32, Position),
+
     #[error("Expected \"set\" or \"const\" as first token on line {0}")]
     ExpectedVarAssignment(LineNumber),
 
@@ -52,33 +63,188 @@ pub enum Error {
 
     #[error("Variable with name {0} already exists!")]
     VariableAlreadyExists(String),
+
+    #[error("Expected a value but was given a {0} at {1}")]
+    ExpectedValue(Token, Position),
+
+    #[error("Expected a builtin but was given a {0} at {1}")]
+    ExpectedBuiltin(Token, Position),
+
+    #[error("Line {0} ended before it was expected to")]
+    UnexpectedEndOfLine(LineNumber),
+
+    #[error("Unexpected argument {0} at {1}")]
+    UnexpectedArgument(Token, Position),
+
+    #[error("{0} does not give back a value at {1}")]
+    BuiltinHasNoValue(Builtin, Position),
+
+    #[error("Expected a {0} but was given a {} at {2}", .1.type_name())]
+    UnexpectedType(&'static str, DataTypes, Position),
+
+    #[error("Unable to read {0:?} as an int at {1}")]
+    InvalidInt(String, Position),
+
+    #[error("Unable to read {0:?} as a float at {1}")]
+    InvalidFloat(String, Position),
+
+    #[error("No input left to read at {0}")]
+    EndOfInput(Position),
+
+    #[error("Failed to read input at {1}: {0}")]
+    InputFailed(String, Position),
+
+    // 0 position of the string
+    // 1 char in the string the error is at
+    #[error("{2} in the string at {0}, char {1}")]
+    InInterpolation(Position, usize, Box<Error>),
+
+    #[error("Missing }} for the {{ in the string at {0}, char {1}")]
+    UnclosedInterpolation(Position, usize),
+
+    #[error("Unmatched }} in the string at {0}, char {1}, use }}}} for a single }}")]
+    UnmatchedBrace(Position, usize),
+
+    #[error("Invalid expression: {0}")]
+    InvalidExpression(String),
+
+    #[error("Invalid escape {0} in the string at {1}, char {2}")]
+    InvalidEscape(String, Position, usize),
+
+    #[error("{0} takes at least {1} argument(s) at {2}")]
+    ExpectedArguments(Builtin, usize, Position),
+
+    #[error("{0} is not defined for {1} at {2}")]
+    OutOfDomain(Builtin, DataTypes, Position),
+
+    #[error("{0} gave back a value too big for an int at {1}")]
+    Overflow(Builtin, Position),
+
+    // 0 "read" or "write"
+    #[error("Permission to {0} {1} was not given at {2}, the host has to allow it like --allow-{0}={1}")]
+    PermissionDenied(&'static str, String, Position),
+
+    #[error("Unable to use {0} at {2}: {1}")]
+    FileSystem(String, String, Position),
+
+    // 0 what was not allowed
+    // 1 the flag allowing it
+    #[error("Not allowed to use {0} at {2}, the host has to allow it like {1}")]
+    NotAllowed(&'static str, &'static str, Position),
+
+    #[error("Environment variable {0} is not set at {1}")]
+    EnvNotSet(String, Position),
+
+    // 1 line and 2 column in the JSON text, starting at 1
+    #[error("{0} at line {1}, column {2} of the JSON at {3}")]
+    InvalidJson(String, usize, usize, Position),
+
+    #[error("Indent {0} is over the limit of {1} at {2}")]
+    IndentTooBig(i32, usize, Position),
+
+    #[error("No key {0:?} in the map at {1}")]
+    MissingKey(String, Position),
+
+    #[error("Index {0} is out of range for a length of {1} at {2}")]
+    IndexOutOfRange(i32, usize, Position),
+
+    #[error("Cannot split on an empty separator at {0}")]
+    EmptySeparator(Position),
+
+    // 1 position of the format builtin
+    // 2 char in the format string the error is at
+    #[error("{0} in the format string at {1}, char {2}")]
+    InvalidFormat(String, Position, usize),
+
+    // 1 line in the CSV text, starting at 1
+    #[error("{0} on line {1} of the CSV at {2}")]
+    InvalidCsv(String, usize, Position),
+
+    #[error("{0:?} can't be used as a CSV delimiter at {1}, it has to be a single char other than a quote or a new line")]
+    InvalidDelimiter(String, Position),
+
+    #[error("Expected the low end {0} to be at most the high end {1} at {2}")]
+    InvalidRange(i32, i32, Position),
+
+    #[error("{0} was given an empty array at {1}")]
+    EmptyArray(Builtin, Position),
 }
 
-#[derive(Default)]
 pub struct Lexer {
     current_line: Line,
+    output: Box<dyn OutputSink>,
+    input: Box<dyn InputSource>,
+    permissions: Permissions,
+    args: Vec<String>,
+    rng: Rng,
+}
+
+impl Default for Lexer {
+    fn default() -> Lexer {
+        Lexer {
+            current_line: Line::default(),
+            output: Box::new(StdoutSink),
+            input: Box::new(StdinSource),
+            permissions: Permissions::default(),
+            args: Vec::new(),
+            rng: Rng::default(),
+        }
+    }
 }
 
 impl Lexer {
+    pub fn set_output(&mut self, output: Box<dyn OutputSink>) {
+        self.output = output;
+    }
+
+    pub fn set_input(&mut self, input: Box<dyn InputSource>) {
+        self.input = input;
+    }
+
+    pub fn set_permissions(&mut self, permissions: Permissions) {
+        self.permissions = permissions;
+    }
+
+    pub fn permissions(&self) -> &Permissions {
+        &self.permissions
+    }
+
+    pub fn set_args(&mut self, args: Vec<String>) {
+        self.args = args;
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
     pub fn lexerize(
         &mut self,
         line: Line,
         variables: &mut Vec<Variable>,
     ) -> Result<BaseLexingReturn> {
-        let math = math::Math::new(&variables);
-
-        match math.is_valid_line(&line) {
-            Ok(_) => {
-                let execute = math.execute(&line)?;
-                match &execute {
-                    BaseLexingReturn::Variable(var) => variables.push(var.clone()),
-                    _ => {}
-                }
-                return Ok(execute);
-            }
-            Err(e) => return Err(e),
+        let mut builtins = builtins::Builtins::new(
+            variables,
+            self.output.as_mut(),
+            self.input.as_mut(),
+            &self.permissions,
+            &self.args,
+            &mut self.rng,
+        );
+        let mut values = values::Values::new(variables);
+        let execute = if builtins.is_valid_line(&line).is_ok() {
+            builtins.execute(&line)?
+        } else if values.is_valid_line(&line).is_ok() {
+            values.execute(&line)?
+        } else {
+            let mut math = math::Math::new(variables);
+            math.is_valid_line(&line)?;
+            math.execute(&line)?
+        };
+
+        if let BaseLexingReturn::Variable(var) = &execute {
+            variables.push(var.clone());
         }
 
-        Err(Error::Unknown(line.number))
+        Ok(execute)
     }
 }
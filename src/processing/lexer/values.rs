@@ -0,0 +1,227 @@
+use super::math::Math;
+use super::Token;
+use super::{BaseLexing, BaseLexingReturn, Error, Result};
+use crate::processing::parser;
+use crate::processing::syntax_elements::{DataTypes, Position, Variable};
+use crate::processing::types::Line;
+use crate::processing::utils::variables::declare_variable;
+
+/// Branch of the lexer for lines that are a single value, like `set x = 2`
+pub struct Values {
+    variables: Vec<Variable>,
+}
+
+impl Values {
+    pub fn new(variables: &[Variable]) -> Values {
+        Values {
+            variables: variables.to_vec(),
+        }
+    }
+}
+
+impl BaseLexing for Values {
+    fn is_valid_line(&self, line: &Line) -> Result<()> {
+        match self.statement(line)?.as_slice() {
+            [Token::Int(_, _)]
+            | [Token::Float(_, _)]
+            | [Token::Bool(_, _)]
+            | [Token::None(_)]
+            | [Token::Str(_, _)]
+            | [Token::RawStr(_, _)]
+            | [Token::Variable(_, _)] => Ok(()),
+            [] => Err(Error::UnexpectedEndOfLine(line.number)),
+            [first, ..] => Err(Error::ExpectedValue(first.clone(), first.get_pos())),
+        }
+    }
+
+    fn execute(&mut self, line: &Line) -> Result<BaseLexingReturn> {
+        let value = evaluate(&self.statement(line)?, line, &self.variables)?;
+
+        if !self.is_assigning_to_variable(line) {
+            return Ok(BaseLexingReturn::Value(value));
+        }
+
+        let variable = declare_variable(line, &self.variables, value)?;
+        Ok(BaseLexingReturn::Variable(variable))
+    }
+}
+
+/// Works out the value of the tokens making up an argument
+///
+/// EXAMPLE:
+///     ["x"] -> value of x
+///     ["1", "+", "2"] -> Int(3)
+pub(super) fn evaluate(tokens: &[Token], line: &Line, variables: &[Variable]) -> Result<DataTypes> {
+    match tokens {
+        [] => Err(Error::UnexpectedEndOfLine(line.number)),
+        [Token::Int(value, _)] => Ok(DataTypes::Int(*value)),
+        [Token::Float(value, _)] => Ok(DataTypes::Float(*value)),
+        [Token::Bool(value, _)] => Ok(DataTypes::Bool(*value)),
+        [Token::None(_)] => Ok(DataTypes::None),
+        [Token::Str(text, pos)] => {
+            let mut evaluate_expression = |tokens: &[Token]| evaluate(tokens, line, variables);
+            let text = interpolate(text, pos, line, variables, &mut evaluate_expression)?;
+            Ok(DataTypes::Str(text))
+        }
+        [Token::RawStr(text, _)] => Ok(DataTypes::Str(text.to_string())),
+        [Token::Variable(name, _)] => match variables.iter().find(|v| &v.name == name) {
+            Some(variable) => Ok(variable.value.clone()),
+            None => Err(Error::ExpectedToFindVarValue(name.to_string())),
+        },
+        [token] => Err(Error::ExpectedValue(token.clone(), token.get_pos())),
+        _ => {
+            let sub_line = Line::new(
+                tokens.to_vec(),
+                line.base_body.clone(),
+                line.split_body.clone(),
+                line.number,
+            );
+            let mut math = Math::new(variables);
+            math.is_valid_line(&sub_line)?;
+
+            match math.execute(&sub_line)? {
+                BaseLexingReturn::Int(value) => Ok(DataTypes::Int(value)),
+                _ => Err(Error::FailedToInferType),
+            }
+        }
+    }
+}
+
+/// Fills in the `{expression}` parts of a string literal, `{{` and `}}` stand for the braces themselves
+/// and escapes like `\n` are replaced by the char they stand for
+///
+/// `evaluate_expression` works out the tokens of each expression
+///
+/// EXAMPLE:
+///     "x = {x},\tsum = {x + 1}" with x = 2
+///     "x = 2,    sum = 3"
+pub(super) fn interpolate(
+    text: &str,
+    pos: &Position,
+    line: &Line,
+    variables: &[Variable],
+    evaluate_expression: &mut dyn FnMut(&[Token]) -> Result<DataTypes>,
+) -> Result<String> {
+    let mut interpolated = String::new();
+    let mut chars = text.chars().enumerate().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek().map(|(_, next)| *next) == Some(c) => {
+                chars.next();
+                interpolated.push(c);
+            }
+            '{' => {
+                let mut expression = String::new();
+                let mut closed = false;
+                for (_, c) in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    expression.push(c);
+                }
+
+                if !closed {
+                    return Err(Error::UnclosedInterpolation(pos.clone(), index));
+                }
+
+                let value =
+                    evaluate_interpolation(&expression, line, variables, evaluate_expression)
+                        .map_err(|e| Error::InInterpolation(pos.clone(), index + 1, Box::new(e)))?;
+                interpolated.push_str(&value.to_string());
+            }
+            '}' => return Err(Error::UnmatchedBrace(pos.clone(), index)),
+            '\\' => interpolated.push(read_escape(&mut chars, pos, index)?),
+            _ => interpolated.push(c),
+        }
+    }
+
+    Ok(interpolated)
+}
+
+/// Replaces the escapes of a string literal without filling in any `{expression}`
+///
+/// EXAMPLE:
+///     "{:>8}\\n" -> "{:>8}\n"
+pub(super) fn unescape(text: &str, pos: &Position) -> Result<String> {
+    let mut unescaped = String::new();
+    let mut chars = text.chars().enumerate();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => unescaped.push(read_escape(&mut chars, pos, index)?),
+            _ => unescaped.push(c),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+/// Reads the escape after a `\`, `index` being where the `\` is in the string
+///
+/// EXAMPLE:
+///     "n" -> '\n'
+///     "u{1F600}" -> '😀'
+fn read_escape(
+    chars: &mut impl Iterator<Item = (usize, char)>,
+    pos: &Position,
+    index: usize,
+) -> Result<char> {
+    let invalid = |escape: String| Error::InvalidEscape(escape, pos.clone(), index);
+
+    let escape = match chars.next() {
+        Some((_, c)) => c,
+        None => return Err(invalid("\\".to_string())),
+    };
+
+    match escape {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        '0' => Ok('\0'),
+        '"' => Ok('"'),
+        '\\' => Ok('\\'),
+        'u' => {
+            if chars.next().map(|(_, c)| c) != Some('{') {
+                return Err(invalid("\\u".to_string()));
+            }
+
+            let mut hex = String::new();
+            let mut closed = false;
+            for (_, c) in chars.by_ref() {
+                if c == '}' {
+                    closed = true;
+                    break;
+                }
+                hex.push(c);
+            }
+
+            let code = match u32::from_str_radix(&hex, 16) {
+                Ok(code) if closed && hex.len() <= 6 => char::from_u32(code),
+                _ => None,
+            };
+
+            code.ok_or_else(|| invalid(format!("\\u{{{}}}", hex)))
+        }
+        other => Err(invalid(format!("\\{}", other))),
+    }
+}
+
+fn evaluate_interpolation(
+    expression: &str,
+    line: &Line,
+    variables: &[Variable],
+    evaluate_expression: &mut dyn FnMut(&[Token]) -> Result<DataTypes>,
+) -> Result<DataTypes> {
+    let split = parser::split(expression);
+    let tokens = match parser::tokenize(&split, line.number, variables) {
+        Ok(tokens) => tokens,
+        Err(parser::Error::InvalidToken(name, _)) => {
+            return Err(Error::ExpectedToFindVarValue(name))
+        }
+        Err(e) => return Err(Error::InvalidExpression(e.to_string())),
+    };
+
+    evaluate_expression(&tokens)
+}
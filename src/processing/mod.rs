@@ -2,9 +2,12 @@ mod lexer;
 mod syntax_elements;
 mod utils;
 
+pub mod io;
 pub mod parser;
+pub mod permissions;
 pub mod types;
 
 
 #[cfg(test)]
+#[allow(clippy::approx_constant, clippy::field_reassign_with_default)]
 mod tests;
@@ -0,0 +1,269 @@
+use super::{Error, Parser, Result};
+use crate::processing::lexer::{self, base::BaseLexingReturn};
+use crate::processing::syntax_elements::{DataTypes, Position, Variable};
+use crate::processing::types::{Line, LineNumber, LineSplitBody, VariableName};
+use crate::processing::utils::variables::var_exists;
+
+/// Names a declaration binds at once, `_` skips a value
+///
+/// EXAMPLE:
+///     "(a, b)" -> Tuple(["a", "b"])
+///     "[first, rest..]" -> Array(["first"], Some("rest"))
+///     "Point { x, y }" -> Struct("Point", ["x", "y"])
+#[derive(PartialEq, Debug)]
+enum Pattern {
+    Tuple(Vec<VariableName>),
+    Array(Vec<VariableName>, Option<VariableName>),
+    Struct(String, Vec<VariableName>),
+}
+
+impl Parser {
+    /// Checks if the line declares a pattern rather than a single name, like `set (a, b) = (1, 2)`
+    pub(super) fn is_destructuring(split: &LineSplitBody) -> bool {
+        let (_, declaration) = strip_export(split);
+        match declaration {
+            [keyword, target, ..] if keyword == "set" || keyword == "const" => {
+                target.starts_with('(')
+                    || target.starts_with('[')
+                    || target.contains('{')
+                    || declaration.get(2).is_some_and(|next| next.starts_with('{'))
+            }
+            _ => false,
+        }
+    }
+
+    /// Works out the value after the `=` and declares every name of the pattern from it
+    pub(super) fn destructure(
+        &mut self,
+        split: &LineSplitBody,
+        line_number: LineNumber,
+    ) -> Result<BaseLexingReturn> {
+        let (exporting, declaration) = strip_export(split);
+        let invalid = || Error::InvalidPattern(line_number, declaration.join(" "));
+        let assignment = declaration
+            .iter()
+            .position(|word| word == "=")
+            .ok_or_else(invalid)?;
+        let pattern_text = declaration[1..assignment].join(" ");
+        let pattern = read_pattern(&pattern_text).ok_or_else(invalid)?;
+
+        let value_start = split.len() - declaration.len() + assignment + 1;
+        let pos = Position::new(line_number, value_start as LineNumber);
+        let value = self.evaluate_words(&declaration[assignment + 1..], line_number)?;
+        let bindings = bind(&pattern, &pattern_text, value, line_number, &pos)?;
+
+        let mutable = declaration[0] == "set";
+        for (name, value) in bindings {
+            if var_exists(&self.variables, &name) {
+                return Err(Error::Runtime(
+                    line_number,
+                    lexer::Error::VariableAlreadyExists(name),
+                ));
+            }
+
+            if exporting {
+                self.exported.push(name.clone());
+            }
+            self.variables
+                .push(Variable::from_data_type(name, value, mutable));
+        }
+
+        Ok(BaseLexingReturn::Empty)
+    }
+
+    /// Works out the value of the words, a `(a, b)` being an array of the value of each part
+    fn evaluate_words(&mut self, words: &[String], line_number: LineNumber) -> Result<DataTypes> {
+        let text = words.join(" ");
+        if let Some(inner) = text
+            .strip_prefix('(')
+            .and_then(|text| text.strip_suffix(')'))
+        {
+            let values = split_on_commas(inner)
+                .iter()
+                .map(|part| self.evaluate_words(&super::split(part), line_number))
+                .collect::<Result<Vec<DataTypes>>>()?;
+            return Ok(DataTypes::Array(values));
+        }
+
+        let words = words.to_vec();
+        let tokenized = self.tokenize(&words, line_number)?;
+        let line = Line::new(tokenized, self.current_line.clone(), words, line_number);
+        let result = self
+            .lexer
+            .lexerize(line, &mut self.variables)
+            .map_err(|e| Error::Runtime(line_number, e))?;
+
+        match result {
+            BaseLexingReturn::Value(value) => Ok(value),
+            BaseLexingReturn::Int(value) => Ok(DataTypes::Int(value)),
+            _ => Err(Error::InvalidPattern(line_number, text)),
+        }
+    }
+}
+
+/// Takes `pub` or `export` off the front of the line, saying if it was there
+fn strip_export(split: &LineSplitBody) -> (bool, &[String]) {
+    match split.first().map(|word| word.as_str()) {
+        Some("pub") | Some("export") => (true, &split[1..]),
+        _ => (false, split),
+    }
+}
+
+fn read_pattern(text: &str) -> Option<Pattern> {
+    if let Some(inner) = text
+        .strip_prefix('(')
+        .and_then(|text| text.strip_suffix(')'))
+    {
+        return Some(Pattern::Tuple(read_names(inner)?));
+    }
+
+    if let Some(inner) = text
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+    {
+        let mut names = read_names_allowing_rest(inner)?;
+        let rest = match names.last() {
+            Some(last) if last.ends_with("..") => {
+                let rest = names.pop()?.trim_end_matches("..").to_string();
+                if !is_name(&rest) {
+                    return None;
+                }
+                Some(rest)
+            }
+            _ => None,
+        };
+        if names.iter().any(|name| !is_name(name)) {
+            return None;
+        }
+        return Some(Pattern::Array(names, rest));
+    }
+
+    let (name, fields) = text.strip_suffix('}')?.split_once('{')?;
+    let name = name.trim();
+    if !is_name(name) || name == "_" {
+        return None;
+    }
+    Some(Pattern::Struct(name.to_string(), read_names(fields)?))
+}
+
+/// Reads the names between the commas, every one has to be a valid name
+fn read_names(text: &str) -> Option<Vec<VariableName>> {
+    let names = read_names_allowing_rest(text)?;
+    names.iter().all(|name| is_name(name)).then_some(names)
+}
+
+fn read_names_allowing_rest(text: &str) -> Option<Vec<VariableName>> {
+    let names: Vec<VariableName> = text
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .collect();
+    let mut seen = Vec::new();
+    for name in &names {
+        if name != "_" && seen.contains(&name) {
+            return None;
+        }
+        seen.push(name);
+    }
+
+    Some(names)
+}
+
+fn is_name(name: &str) -> bool {
+    let starts_well = name.starts_with(|c: char| c.is_alphabetic() || c == '_');
+    starts_well && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Pairs the names of the pattern with the parts of the value, leaving out the ones named `_`
+fn bind(
+    pattern: &Pattern,
+    pattern_text: &str,
+    value: DataTypes,
+    line_number: LineNumber,
+    pos: &Position,
+) -> Result<Vec<(VariableName, DataTypes)>> {
+    let unexpected = |expected, value: DataTypes| {
+        Error::Runtime(
+            line_number,
+            lexer::Error::UnexpectedType(expected, value, pos.clone()),
+        )
+    };
+    let wrong_length = |expected: String, given| {
+        Error::PatternMismatch(line_number, pattern_text.to_string(), expected, given)
+    };
+
+    let bindings = match (pattern, value) {
+        (Pattern::Tuple(names), DataTypes::Array(values))
+        | (Pattern::Array(names, None), DataTypes::Array(values)) => {
+            if names.len() != values.len() {
+                return Err(wrong_length(names.len().to_string(), values.len()));
+            }
+            names.iter().cloned().zip(values).collect()
+        }
+        (Pattern::Array(names, Some(rest)), DataTypes::Array(mut values)) => {
+            if values.len() < names.len() {
+                return Err(wrong_length(
+                    format!("at least {}", names.len()),
+                    values.len(),
+                ));
+            }
+            let rest_values = values.split_off(names.len());
+            let mut bindings: Vec<(VariableName, DataTypes)> =
+                names.iter().cloned().zip(values).collect();
+            bindings.push((rest.to_string(), DataTypes::Array(rest_values)));
+            bindings
+        }
+        (Pattern::Struct(_, fields), DataTypes::Map(entries)) => {
+            let mut bindings = Vec::new();
+            for field in fields.iter().filter(|field| *field != "_") {
+                match entries.iter().find(|(key, _)| key == field) {
+                    Some((_, value)) => bindings.push((field.to_string(), value.clone())),
+                    None => {
+                        return Err(Error::Runtime(
+                            line_number,
+                            lexer::Error::MissingKey(field.to_string(), pos.clone()),
+                        ))
+                    }
+                }
+            }
+            bindings
+        }
+        (Pattern::Struct(_, _), other) => return Err(unexpected("map", other)),
+        (_, other) => return Err(unexpected("array", other)),
+    };
+
+    Ok(bindings
+        .into_iter()
+        .filter(|(name, _)| name != "_")
+        .collect())
+}
+
+/// Splits the text on the commas that are not inside a string or another `(...)`
+///
+/// EXAMPLE:
+///     "1, \"a, b\", (2, 3)"
+///     ["1", "\"a, b\"", "(2, 3)"]
+fn split_on_commas(text: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut depth = 0;
+
+    for c in text.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        parts.last_mut().unwrap().push(c);
+    }
+
+    parts
+}
@@ -1,18 +1,89 @@
 use super::{
+    io::{InputSource, OutputSink},
     lexer,
-    syntax_elements::{Position, Token, Variable},
+    permissions::Permissions,
+    syntax_elements::{Builtin, Position, Token, Variable},
     types::{Line, LineNumber},
     utils::variables::{look_variable, var_exists},
 };
+use lexer::base::BaseLexingReturn;
+use modules::{ImportScope, Module};
+use report::{ExecutionReport, StatementReport};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 use super::types::{LineSplitBody, LineTokenizedBody};
 
+mod destructure;
+mod modules;
+pub mod report;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, PartialEq)]
 pub enum Error {
     #[error("Invalid token {0} at pos {1}")]
     InvalidToken(String, String),
+
+    #[error("Unterminated string {0} at pos {1}")]
+    UnterminatedString(String, String),
+
+    #[error("Error on line {0}: {1}")]
+    Runtime(LineNumber, lexer::Error),
+
+    /// Every error hit while running with `ErrorPolicy::Continue`, in line order
+    #[error("{} line(s) failed to run:\n{}", .0.len(), join_errors(.0))]
+    Failures(Vec<Error>),
+
+    #[error("Invalid import on line {0}: {1}, expected import \"file\" [as name] or from file import names")]
+    InvalidImport(LineNumber, String),
+
+    #[error("Unable to import {1} on line {0}: {2}")]
+    ImportFailed(LineNumber, String, String),
+
+    #[error("Not allowed to import {1} on line {0}, it is outside the program and the host has to allow reading it like --allow-read={1}")]
+    ImportNotAllowed(LineNumber, String),
+
+    #[error("Import cycle on line {0}: {1}")]
+    ImportCycle(LineNumber, String),
+
+    #[error("{1} is not declared by {2}, imported on line {0}")]
+    NotInModule(LineNumber, String, String),
+
+    #[error("In {0}: {1}")]
+    InModule(String, Box<Error>),
+
+    #[error("Only variable declarations can be pub, line {0}")]
+    InvalidExport(LineNumber),
+
+    #[error("{1} is private to {2}, it has to be declared pub to be imported, line {0}")]
+    PrivateMember(LineNumber, String, String),
+
+    #[error("Invalid declaration on line {0}: {1}, expected set (a, b), set [a, rest..] or set Name {{ a, b }} = value")]
+    InvalidPattern(LineNumber, String),
+
+    // 2 how many values the pattern takes
+    // 3 how many it was given
+    #[error("{1} takes {2} value(s) but was given {3} on line {0}")]
+    PatternMismatch(LineNumber, String, String, usize),
+}
+
+fn join_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// What `Parser::execute` does when a line fails
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum ErrorPolicy {
+    /// Stop at the first failing line and return its error
+    #[default]
+    Abort,
+    /// Keep running the following lines and return every error at the end
+    Continue,
 }
 
 #[derive(Default)]
@@ -21,31 +92,186 @@ pub struct Parser {
     pub(super) split_line: LineSplitBody,
     pub(super) tokenized_line: LineTokenizedBody,
     pub(super) variables: Vec<Variable>,
+    pub(super) error_policy: ErrorPolicy,
+    pub(super) lexer: lexer::Lexer,
+    /// File being run, imports are relative to it
+    pub(super) path: Option<PathBuf>,
+    /// Files that are running, from the first one to the one importing right now
+    pub(super) importing: Vec<PathBuf>,
+    pub(super) modules: HashMap<PathBuf, Module>,
+    /// Names of the variables that came from imports rather than being declared
+    pub(super) imported: Vec<String>,
+    /// Names of the variables declared pub, the only ones other files can import
+    pub(super) exported: Vec<String>,
+    /// Members that were left out of a namespace import for being private, with the module they are in
+    ///
+    /// EXAMPLE:
+    ///     "import util" with a private x
+    ///     ("util.x", "x", "util.pl")
+    pub(super) private_members: Vec<(String, String, String)>,
+    /// Directories to look for imports in after the directory of the importing file
+    pub(super) search_paths: Vec<PathBuf>,
+    /// Modules imported by name from anywhere
+    pub(super) packages: HashMap<String, PathBuf>,
+    /// Search paths and packages only the files of the project at the root can use
+    pub(super) projects: HashMap<PathBuf, ImportScope>,
+    /// Status the script asked to stop with, nothing else runs once it is set
+    pub(super) exit_code: Option<i32>,
 }
 
 impl Parser {
-    pub fn parse(&mut self, body: &str) -> Result<()> {
+    pub fn set_error_policy(&mut self, error_policy: ErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Sends whatever the script prints to `output` instead of stdout
+    pub fn set_output(&mut self, output: Box<dyn OutputSink>) {
+        self.lexer.set_output(output);
+    }
+
+    /// Reads whatever the script asks for from `input` instead of stdin
+    pub fn set_input(&mut self, input: Box<dyn InputSource>) {
+        self.lexer.set_input(input);
+    }
+
+    /// Sets what the script is allowed to touch, by default it can't use the file system at all
+    pub fn set_permissions(&mut self, permissions: Permissions) {
+        self.lexer.set_permissions(permissions);
+    }
+
+    /// Sets what `env.args` gives back
+    pub fn set_args(&mut self, args: Vec<String>) {
+        self.lexer.set_args(args);
+    }
+
+    /// Seeds the `random` builtins so every run gives back the same numbers, by default they are seeded from the clock
+    pub fn set_seed(&mut self, seed: u64) {
+        self.lexer.set_seed(seed);
+    }
+
+    /// Runs every line of the body and returns the report, or the error if any line failed
+    pub fn parse(&mut self, body: &str) -> Result<ExecutionReport> {
+        self.execute(body).into_result(self.error_policy)
+    }
+
+    /// Runs every line of the body and reports what each of them did
+    ///
+    /// Failed lines are part of the report, execution stops after the first one
+    /// unless the error policy is `ErrorPolicy::Continue`, and always stops at an `exit`
+    pub fn execute(&mut self, body: &str) -> ExecutionReport {
         let lines = self.separate_to_lines(body);
-        let mut lexer = lexer::Lexer::default();
+        let mut report = ExecutionReport::default();
 
-        for (line_number, line) in lines.iter().enumerate() {
-            self.current_line = line.to_string();
+        for (line_number, end_line_number, line) in lines {
+            // an exit in an imported module stops the files importing it too
+            if self.exit_code.is_some() {
+                break;
+            }
+
+            self.current_line = line.clone();
             let split = self.split();
-            let line_number = line_number as LineNumber;
-            let _tokenized = self.tokenize(&split, line_number)?;
-            let line = Line::new(_tokenized, line.to_string(), split, line_number);
-            let lexerize = lexer.lexerize(line, &mut self.variables);
-            match lexerize {
-                Ok(b) => println!("Output: {:?}", b),
-                Err(e) => println!("{}", e),
+
+            // blank lines have nothing to run
+            if split.is_empty() {
+                continue;
+            }
+
+            let end = Position::new(end_line_number, words_on_last_line(&line, &split));
+            let range = Position::new(line_number, 0)..end;
+            let result = self.parse_line(split, line_number);
+            let failed = result.is_err();
+            if let Ok(BaseLexingReturn::Exit(code)) = result {
+                self.exit_code = Some(code);
+            }
+
+            report.statements.push(StatementReport {
+                source: line,
+                range,
+                result,
+            });
+
+            if failed && self.error_policy == ErrorPolicy::Abort {
+                break;
+            }
+        }
+
+        report.exit_code = self.exit_code;
+        report
+    }
+
+    fn parse_line(
+        &mut self,
+        split: LineSplitBody,
+        line_number: LineNumber,
+    ) -> Result<BaseLexingReturn> {
+        if Parser::is_import(&split) {
+            return self.import(&split, line_number);
+        }
+
+        if Parser::is_destructuring(&split) {
+            return self.destructure(&split, line_number);
+        }
+
+        let mut tokenized = self.tokenize(&split, line_number)?;
+        let exporting = matches!(tokenized.first(), Some(Token::Export(_)));
+        if exporting {
+            tokenized.remove(0);
+
+            let declaring = matches!(
+                tokenized.first(),
+                Some(Token::MutVarDeclaration(_)) | Some(Token::ImmutVarDeclaration(_))
+            );
+            if !declaring {
+                return Err(Error::InvalidExport(line_number));
             }
         }
 
-        Ok(())
+        let line = Line::new(tokenized, self.current_line.clone(), split, line_number);
+        let result = self
+            .lexer
+            .lexerize(line, &mut self.variables)
+            .map_err(|e| Error::Runtime(line_number, e))?;
+
+        if let (BaseLexingReturn::Variable(variable), true) = (&result, exporting) {
+            self.exported.push(variable.name.clone());
+        }
+
+        Ok(result)
     }
 
-    fn separate_to_lines(&mut self, body: &str) -> Vec<String> {
-        let lines: Vec<String> = body.lines().map(|line| line.to_string()).collect();
+    /// Splits the body into the lines to run, each with the numbers of the lines it starts and ends on, counting from
+    /// 1 like an editor does
+    ///
+    /// A string that is still open at the end of a line carries on to the next one,
+    /// so a multi-line string ends up in a single line
+    fn separate_to_lines(&mut self, body: &str) -> Vec<(LineNumber, LineNumber, String)> {
+        let mut lines = Vec::new();
+        let mut open_string: Option<(LineNumber, String)> = None;
+        let mut last_line_number = 0;
+
+        for (line_number, line) in body.lines().enumerate() {
+            last_line_number = line_number as LineNumber + 1;
+            let (start, text) = match open_string.take() {
+                Some((start, mut text)) => {
+                    text.push('\n');
+                    text.push_str(line);
+                    (start, text)
+                }
+                None => (last_line_number, line.to_string()),
+            };
+
+            if ends_inside_string(&text) {
+                open_string = Some((start, text));
+            } else {
+                lines.push((start, last_line_number, text));
+            }
+        }
+
+        // never closed, the tokenizer reports it
+        if let Some((start, text)) = open_string {
+            lines.push((start, last_line_number, text));
+        }
+
         lines
     }
 
@@ -55,10 +281,7 @@ impl Parser {
     ///     "let x = 1"
     ///     ["let", "x", "=", "1"]
     pub(super) fn split(&mut self) -> LineSplitBody {
-        self.current_line
-            .split(' ')
-            .map(|s| s.to_string())
-            .collect()
+        split(&self.current_line)
     }
 
     pub(super) fn tokenize(
@@ -66,46 +289,194 @@ impl Parser {
         split_line: &LineSplitBody,
         line_number: LineNumber,
     ) -> Result<LineTokenizedBody> {
-        let mut t: LineTokenizedBody = Vec::new();
-
-        for (index, token) in split_line.iter().enumerate() {
-            let pos = Position::new(line_number, index as LineNumber);
-            match token.as_str() {
-                "+" => t.push(Token::Add(pos)),
-                "-" => t.push(Token::Subtract(pos)),
-                "/" => t.push(Token::Divide(pos)),
-                "*" => t.push(Token::Multiply(pos)),
-                "\n" => t.push(Token::Eol(pos)),
-                "//" => t.push(Token::SingleComment(pos)),
-                "set" => t.push(Token::MutVarDeclaration(pos)),
-                "const" => t.push(Token::ImmutVarDeclaration(pos)),
-                "=" => t.push(Token::Assignment(pos)),
-                _ if self.is_int(token) => t.push(Token::Int(token.parse().unwrap(), pos)),
-                _ if self.is_variable(&t) => t.push(Token::Variable(token.to_string(), pos)),
-                _ => {
-                    if !var_exists(&self.variables, token.as_str()) {
-                        return Err(Error::InvalidToken(token.to_string(), index.to_string()));
-                    }
-
-                    t.push(Token::Variable(token.to_string(), pos))
+        match tokenize(split_line, line_number, &self.variables) {
+            Err(Error::InvalidToken(token, index)) => {
+                match self.private_members.iter().find(|(name, _, _)| *name == token) {
+                    Some((_, member, module)) => Err(Error::PrivateMember(
+                        line_number,
+                        member.to_string(),
+                        module.to_string(),
+                    )),
+                    None => Err(Error::InvalidToken(token, index)),
                 }
             }
+            tokenized => tokenized,
         }
-        Ok(t)
     }
+}
+
+/// Splits a line on spaces and tabs, keeping quoted strings together
+///
+/// EXAMPLE:
+///     "print "a b" x"
+///     ["print", "\"a b\"", "x"]
+pub(super) fn split(line: &str) -> LineSplitBody {
+    split_words(line).0
+}
+
+/// Counts the words of a line ending after its last new line, the position after its last word on the line it ends on
+///
+/// EXAMPLE:
+///     "println \"a\nb\" x"
+///     2
+fn words_on_last_line(line: &str, split: &LineSplitBody) -> LineNumber {
+    let last_line_start = match line.rfind('\n') {
+        Some(index) => index,
+        None => return split.len() as LineNumber,
+    };
 
-    fn is_int(&self, token: &str) -> bool {
-        token.parse::<i32>().is_ok()
+    let mut offset = 0;
+    let mut count = 0;
+    for word in split {
+        // the words are taken from the line as they are, so each one is found after the one before it
+        offset += line[offset..].find(word.as_str()).unwrap_or(0) + word.len();
+        if offset > last_line_start {
+            count += 1;
+        }
     }
 
-    fn is_variable(&self, tokens: &LineTokenizedBody) -> bool {
-        if let Some(last) = tokens.last() {
-            matches!(
-                last,
-                Token::MutVarDeclaration(_) | Token::ImmutVarDeclaration(_)
-            )
-        } else {
-            false
+    count
+}
+
+/// Checks if a string is still open at the end of the line
+fn ends_inside_string(line: &str) -> bool {
+    split_words(line).1
+}
+
+fn split_words(line: &str) -> (LineSplitBody, bool) {
+    let mut split = Vec::new();
+    let mut current = String::new();
+    let mut in_string = false;
+    let mut raw = false;
+    let mut escaped = false;
+
+    for c in line.chars() {
+        if in_string {
+            current.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if !raw => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
         }
+
+        match c {
+            ' ' | '\t' if !current.is_empty() => split.push(std::mem::take(&mut current)),
+            ' ' | '\t' => {}
+            '"' => {
+                in_string = true;
+                raw = current == "r";
+                current.push(c);
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if !current.is_empty() {
+        split.push(current);
+    }
+
+    (split, in_string)
+}
+
+/// Converts the split line to tokens, names that are not declared by the line must already be in `variables`
+pub(super) fn tokenize(
+    split_line: &LineSplitBody,
+    line_number: LineNumber,
+    variables: &[Variable],
+) -> Result<LineTokenizedBody> {
+    let mut t: LineTokenizedBody = Vec::new();
+
+    for (index, token) in split_line.iter().enumerate() {
+        let pos = Position::new(line_number, index as LineNumber);
+        match token.as_str() {
+            "+" => t.push(Token::Add(pos)),
+            "-" => t.push(Token::Subtract(pos)),
+            "/" => t.push(Token::Divide(pos)),
+            "*" => t.push(Token::Multiply(pos)),
+            "\n" => t.push(Token::Eol(pos)),
+            "//" => t.push(Token::SingleComment(pos)),
+            "set" => t.push(Token::MutVarDeclaration(pos)),
+            "const" => t.push(Token::ImmutVarDeclaration(pos)),
+            "=" => t.push(Token::Assignment(pos)),
+            "pub" | "export" => t.push(Token::Export(pos)),
+            "true" => t.push(Token::Bool(true, pos)),
+            "false" => t.push(Token::Bool(false, pos)),
+            "none" => t.push(Token::None(pos)),
+            _ if Builtin::from_name(token).is_some() => {
+                t.push(Token::Builtin(Builtin::from_name(token).unwrap(), pos))
+            }
+            _ if token.starts_with('"') || token.starts_with("r\"") => {
+                t.push(read_str(token, pos)?)
+            }
+            _ if is_int(token) => t.push(Token::Int(token.parse().unwrap(), pos)),
+            _ if is_float(token) => t.push(Token::Float(token.parse().unwrap(), pos)),
+            _ if is_variable(&t) => t.push(Token::Variable(token.to_string(), pos)),
+            _ => {
+                if !var_exists(variables, token.as_str()) {
+                    return Err(Error::InvalidToken(token.to_string(), pos.to_string()));
+                }
+
+                t.push(Token::Variable(token.to_string(), pos))
+            }
+        }
+    }
+    Ok(t)
+}
+
+/// Strips the quotes off a string literal, the contents are kept as they were written
+///
+/// EXAMPLE:
+///     "\"a\\tb\"" -> Str("a\\tb")
+///     "r\"a\\tb\"" -> RawStr("a\\tb")
+fn read_str(token: &str, pos: Position) -> Result<Token> {
+    let raw = token.starts_with('r');
+    let contents = if raw { &token[2..] } else { &token[1..] };
+    let mut escaped = false;
+
+    for (index, c) in contents.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if !raw => escaped = true,
+            '"' if index + 1 == contents.len() && raw => {
+                return Ok(Token::RawStr(contents[..index].to_string(), pos))
+            }
+            '"' if index + 1 == contents.len() => {
+                return Ok(Token::Str(contents[..index].to_string(), pos))
+            }
+            // something is stuck to the end of the string
+            '"' => return Err(Error::InvalidToken(token.to_string(), pos.to_string())),
+            _ => {}
+        }
+    }
+
+    Err(Error::UnterminatedString(
+        token.to_string(),
+        pos.to_string(),
+    ))
+}
+
+fn is_int(token: &str) -> bool {
+    token.parse::<i32>().is_ok()
+}
+
+/// Only plain decimals like "2.5" or "-0.5", rust would also take "inf" or "1e3"
+fn is_float(token: &str) -> bool {
+    let digits = token.strip_prefix('-').unwrap_or(token);
+    let plain = digits.contains('.') && digits.chars().all(|c| c.is_ascii_digit() || c == '.');
+
+    plain && token.parse::<f64>().is_ok()
+}
+
+fn is_variable(tokens: &LineTokenizedBody) -> bool {
+    if let Some(last) = tokens.last() {
+        matches!(
+            last,
+            Token::MutVarDeclaration(_) | Token::ImmutVarDeclaration(_)
+        )
+    } else {
+        false
     }
 }
@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::{Error, ErrorPolicy, Parser, Result};
+use crate::processing::lexer::{self, base::BaseLexingReturn};
+use crate::processing::syntax_elements::Variable;
+use crate::processing::types::{LineNumber, LineSplitBody, VariableName};
+use crate::processing::utils::variables::var_exists;
+
+/// What an import line asks for, the module as it was written
+///
+/// EXAMPLE:
+///     "import \"util.pl\" as util" -> Namespace("\"util.pl\"", "util")
+///     "from util import x y" -> Names("util", ["x", "y"])
+#[derive(PartialEq, Debug)]
+enum Import {
+    Namespace(String, VariableName),
+    Names(String, Vec<VariableName>),
+}
+
+/// Everything a module leaves behind once it ran, so a module imported twice only runs once
+#[derive(Clone)]
+pub(crate) struct Module {
+    /// The variables it declared pub
+    variables: Vec<Variable>,
+    /// Names of the variables it declared without pub
+    private: Vec<VariableName>,
+}
+
+/// Where the files of one project look for their imports, so a dependency's sources and dependencies stay its own
+#[derive(Default)]
+pub(crate) struct ImportScope {
+    search_paths: Vec<PathBuf>,
+    packages: HashMap<String, PathBuf>,
+}
+
+impl Parser {
+    /// Sets the file the body comes from, imports are looked for next to it
+    pub fn set_path(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.importing = vec![path.clone()];
+        self.path = Some(path);
+    }
+
+    /// Adds a directory to look for imports in when they are not next to the importing file
+    pub fn add_search_path(&mut self, path: &Path) {
+        self.search_paths.push(path.to_path_buf());
+    }
+
+    /// Lets `import name` run the file at `entry`, wherever it is
+    pub fn add_package(&mut self, name: &str, entry: &Path) {
+        self.packages.insert(name.to_string(), entry.to_path_buf());
+    }
+
+    /// Makes the files inside `root` a project of their own, they only see the search paths and packages added for it
+    /// along with the ones for everything
+    pub fn add_project_root(&mut self, root: &Path) {
+        self.projects.entry(root.to_path_buf()).or_default();
+    }
+
+    /// Like `add_search_path`, but only for the files inside the project at `root`
+    pub fn add_project_search_path(&mut self, root: &Path, path: &Path) {
+        let scope = self.projects.entry(root.to_path_buf()).or_default();
+        scope.search_paths.push(path.to_path_buf());
+    }
+
+    /// Like `add_package`, but only for the files inside the project at `root`
+    pub fn add_project_package(&mut self, root: &Path, name: &str, entry: &Path) {
+        let scope = self.projects.entry(root.to_path_buf()).or_default();
+        scope.packages.insert(name.to_string(), entry.to_path_buf());
+    }
+
+    /// Checks if the line is an import, which the parser deals with rather than the lexer
+    pub(super) fn is_import(split: &LineSplitBody) -> bool {
+        matches!(
+            split.first().map(|word| word.as_str()),
+            Some("import") | Some("from")
+        )
+    }
+
+    /// Runs the module the line imports and declares the variables it asks for
+    pub(super) fn import(
+        &mut self,
+        split: &LineSplitBody,
+        line_number: LineNumber,
+    ) -> Result<BaseLexingReturn> {
+        let declared = match read_import(split) {
+            Some(Import::Namespace(name, alias)) => {
+                let module = self.load_module(&name, line_number)?;
+                for member in module.private {
+                    let full_name = format!("{}.{}", alias, member);
+                    self.private_members
+                        .push((full_name, member, show_module(&name)));
+                }
+
+                module
+                    .variables
+                    .into_iter()
+                    .map(|variable| {
+                        let name = format!("{}.{}", alias, variable.name);
+                        Variable::from_data_type(name, variable.value, false)
+                    })
+                    .collect::<Vec<Variable>>()
+            }
+            Some(Import::Names(module_name, names)) => {
+                let module = self.load_module(&module_name, line_number)?;
+                let mut declared = Vec::new();
+                for name in names {
+                    match module.variables.iter().find(|v| v.name == name) {
+                        Some(variable) => declared.push(Variable::from_data_type(
+                            name,
+                            variable.value.clone(),
+                            false,
+                        )),
+                        None if module.private.contains(&name) => {
+                            let module = show_module(&module_name);
+                            return Err(Error::PrivateMember(line_number, name, module));
+                        }
+                        None => {
+                            let module = show_module(&module_name);
+                            return Err(Error::NotInModule(line_number, name, module));
+                        }
+                    }
+                }
+                declared
+            }
+            None => return Err(Error::InvalidImport(line_number, split.join(" "))),
+        };
+
+        for variable in declared {
+            if var_exists(&self.variables, &variable.name) {
+                let name = variable.name;
+                return Err(Error::Runtime(
+                    line_number,
+                    lexer::Error::VariableAlreadyExists(name),
+                ));
+            }
+
+            self.imported.push(variable.name.clone());
+            self.variables.push(variable);
+        }
+
+        Ok(BaseLexingReturn::Empty)
+    }
+
+    /// Runs the module unless it already ran
+    fn load_module(&mut self, module: &str, line_number: LineNumber) -> Result<Module> {
+        let failed = |reason: String| Error::ImportFailed(line_number, show_module(module), reason);
+        let full_path = self.resolve(module).map_err(failed)?;
+        if !self.can_import(&full_path) {
+            let path = full_path.display().to_string();
+            return Err(Error::ImportNotAllowed(line_number, path));
+        }
+
+        if self.importing.contains(&full_path) {
+            let mut chain = self.importing.clone();
+            chain.push(full_path);
+            let chain: Vec<String> = chain.iter().map(|path| self.show_path(path)).collect();
+            return Err(Error::ImportCycle(line_number, chain.join(" -> ")));
+        }
+
+        if let Some(module) = self.modules.get(&full_path) {
+            return Ok(module.clone());
+        }
+
+        let body = std::fs::read_to_string(&full_path).map_err(|e| failed(e.to_string()))?;
+        let module = self
+            .run_module(&full_path, &body)
+            .map_err(|e| Error::InModule(self.show_path(&full_path), Box::new(e)))?;
+
+        self.modules.insert(full_path, module.clone());
+        Ok(module)
+    }
+
+    /// Finds the file of a module: a package with that name, next to the importing file or in one of the search paths
+    ///
+    /// The error is why it could not be found
+    fn resolve(&self, module: &str) -> std::result::Result<PathBuf, String> {
+        let scope = self.project_scope();
+        let package = scope
+            .and_then(|scope| scope.packages.get(module))
+            .or_else(|| self.packages.get(module));
+        if let Some(entry) = package {
+            return entry.canonicalize().map_err(|e| e.to_string());
+        }
+
+        let path = module_path(module).ok_or_else(|| "Invalid module name".to_string())?;
+        let directory = match &self.path {
+            Some(current) => current.parent().unwrap_or(Path::new(".")).to_path_buf(),
+            None => PathBuf::from("."),
+        };
+
+        let mut candidates = vec![directory.join(&path)];
+        let project_search_paths = scope.into_iter().flat_map(|scope| &scope.search_paths);
+        candidates.extend(
+            project_search_paths
+                .chain(&self.search_paths)
+                .map(|search| search.join(&path)),
+        );
+
+        match candidates.iter().find(|candidate| candidate.is_file()) {
+            Some(found) => found.canonicalize().map_err(|e| e.to_string()),
+            None => Err(format!(
+                "Not found in {}",
+                candidates
+                    .iter()
+                    .map(|candidate| self.show_path(candidate))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// The project of the file importing right now: the one with the deepest root the file is in, or else the
+    /// project of the file that imported it
+    fn project_scope(&self) -> Option<&ImportScope> {
+        self.importing.iter().rev().find_map(|file| {
+            self.projects
+                .iter()
+                .filter(|(root, _)| file.starts_with(root))
+                .max_by_key(|(root, _)| root.components().count())
+                .map(|(_, scope)| scope)
+        })
+    }
+
+    /// Whether the file is part of the program: inside the directory of the first file that ran, a project, a search
+    /// path or the directory of a package. Anywhere else has to be readable like for `fs.read`
+    fn can_import(&self, path: &Path) -> bool {
+        if self.lexer.permissions().can_read(path) {
+            return true;
+        }
+
+        let entry_directory = match self.importing.first() {
+            Some(first) => first.parent().map(Path::to_path_buf),
+            None => std::env::current_dir().ok(),
+        };
+        let scopes = self.projects.values();
+        let search_paths = scopes
+            .clone()
+            .flat_map(|scope| &scope.search_paths)
+            .chain(&self.search_paths)
+            .cloned();
+        let package_directories = scopes
+            .flat_map(|scope| scope.packages.values())
+            .chain(self.packages.values())
+            .filter_map(|entry| entry.parent().map(Path::to_path_buf));
+
+        entry_directory
+            .into_iter()
+            .chain(self.projects.keys().cloned())
+            .chain(search_paths)
+            .chain(package_directories)
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| path.starts_with(root))
+    }
+
+    /// Shows the path relative to the directory of the first file that ran, when it is inside it
+    ///
+    /// EXAMPLE:
+    ///     "/home/ada/project/lib/util.pl" -> "lib/util.pl"
+    fn show_path(&self, path: &Path) -> String {
+        let root = match self.importing.first().and_then(|first| first.parent()) {
+            Some(root) => root.to_path_buf(),
+            None => std::env::current_dir().unwrap_or_default(),
+        };
+
+        path.strip_prefix(&root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Runs the body on its own, keeping what the importing file declared aside until it is done
+    fn run_module(&mut self, path: &Path, body: &str) -> Result<Module> {
+        let variables = std::mem::take(&mut self.variables);
+        let imported = std::mem::take(&mut self.imported);
+        let exported = std::mem::take(&mut self.exported);
+        let private_members = std::mem::take(&mut self.private_members);
+        let current_path = self.path.replace(path.to_path_buf());
+        let current_line = self.current_line.clone();
+        let error_policy = self.error_policy;
+
+        self.error_policy = ErrorPolicy::Abort;
+        self.importing.push(path.to_path_buf());
+        let result = self.execute(body).into_result(ErrorPolicy::Abort);
+        self.importing.pop();
+
+        let module_variables = std::mem::replace(&mut self.variables, variables);
+        let module_imported = std::mem::replace(&mut self.imported, imported);
+        let module_exported = std::mem::replace(&mut self.exported, exported);
+        self.private_members = private_members;
+        self.path = current_path;
+        self.current_line = current_line;
+        self.error_policy = error_policy;
+
+        result?;
+
+        // only what the module declared itself, not what it imported
+        let (variables, private): (Vec<Variable>, Vec<Variable>) = module_variables
+            .into_iter()
+            .filter(|variable| !module_imported.contains(&variable.name))
+            .partition(|variable| module_exported.contains(&variable.name));
+
+        Ok(Module {
+            variables,
+            private: private.into_iter().map(|variable| variable.name).collect(),
+        })
+    }
+}
+
+fn read_import(split: &LineSplitBody) -> Option<Import> {
+    let words: Vec<&str> = split.iter().map(|word| word.as_str()).collect();
+
+    match words.as_slice() {
+        ["import", module] => {
+            let path = module_path(module)?;
+            let alias = path.file_stem()?.to_str()?.to_string();
+            Some(Import::Namespace(module.to_string(), alias))
+        }
+        ["import", module, "as", alias] => {
+            Some(Import::Namespace(module.to_string(), alias.to_string()))
+        }
+        ["from", module, "import", names @ ..] if !names.is_empty() => {
+            let names = names.iter().map(|name| name.to_string()).collect();
+            Some(Import::Names(module.to_string(), names))
+        }
+        _ => None,
+    }
+}
+
+/// A module is either a quoted path or a name standing for the `.pl` file with that name
+///
+/// EXAMPLE:
+///     "\"lib/util.pl\"" -> "lib/util.pl"
+///     "util" -> "util.pl"
+fn module_path(module: &str) -> Option<PathBuf> {
+    match module.strip_prefix('"') {
+        Some(path) => Some(PathBuf::from(path.strip_suffix('"')?)),
+        None => Some(PathBuf::from(format!("{}.pl", module))),
+    }
+}
+
+/// Shows the module the way the user would recognise it
+///
+/// EXAMPLE:
+///     "\"lib/util.pl\"" -> "lib/util.pl"
+///     "util" -> "util.pl"
+fn show_module(module: &str) -> String {
+    match module_path(module) {
+        Some(path) => path.display().to_string(),
+        None => module.to_string(),
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use super::{Error, ErrorPolicy, Result};
+use crate::processing::lexer::base::BaseLexingReturn;
+use crate::processing::syntax_elements::Position;
+
+/// Outcome of running a single line
+#[derive(Debug, PartialEq)]
+pub struct StatementReport {
+    pub source: String,
+    /// From the first token of the line up to, but not including, the position after its last token on the line it
+    /// ends on, which is further down than where it started when it has a multi-line string
+    pub range: Range<Position>,
+    /// The value computed by the line or the variable it declared
+    pub result: Result<BaseLexingReturn>,
+}
+
+/// Every line run by `Parser::execute`, in the order they ran
+#[derive(Debug, PartialEq, Default)]
+pub struct ExecutionReport {
+    pub statements: Vec<StatementReport>,
+    /// Set when the script ended with `exit`
+    pub exit_code: Option<i32>,
+}
+
+impl ExecutionReport {
+    pub fn is_success(&self) -> bool {
+        self.statements
+            .iter()
+            .all(|statement| statement.result.is_ok())
+    }
+
+    pub fn errors(&self) -> Vec<&Error> {
+        self.statements
+            .iter()
+            .filter_map(|statement| statement.result.as_ref().err())
+            .collect()
+    }
+
+    /// Keeps the report if every line succeeded, otherwise returns the error for the failed lines
+    ///
+    /// With `ErrorPolicy::Abort` that is the error of the line execution stopped at,
+    /// with `ErrorPolicy::Continue` it is every error wrapped in `Error::Failures`
+    pub fn into_result(self, error_policy: ErrorPolicy) -> Result<ExecutionReport> {
+        if self.is_success() {
+            return Ok(self);
+        }
+
+        let mut failures: Vec<Error> = self
+            .statements
+            .into_iter()
+            .filter_map(|statement| statement.result.err())
+            .collect();
+
+        match error_policy {
+            ErrorPolicy::Abort => Err(failures.remove(0)),
+            ErrorPolicy::Continue => Err(Error::Failures(failures)),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::{Component, Path, PathBuf};
+
+/// What a part of the file system a script was given access to
+#[derive(Default, Clone, PartialEq, Debug)]
+pub enum Scope {
+    /// Nothing, the default so scripts can't touch anything unless the host allows it
+    #[default]
+    Denied,
+    /// Only these directories and files, along with everything inside them
+    Paths(Vec<PathBuf>),
+    All,
+}
+
+impl Scope {
+    fn allows(&self, path: &Path) -> bool {
+        match self {
+            Scope::Denied => false,
+            Scope::All => true,
+            Scope::Paths(allowed) => {
+                let path = absolute(path);
+                allowed.iter().any(|allowed| path.starts_with(allowed))
+            }
+        }
+    }
+
+    fn add(&mut self, path: &Path) {
+        let path = absolute(path);
+        match self {
+            Scope::Denied => *self = Scope::Paths(vec![path]),
+            Scope::Paths(allowed) => allowed.push(path),
+            Scope::All => {}
+        }
+    }
+}
+
+/// What the host lets a script do outside of its own variables
+///
+/// EXAMPLE:
+///     --allow-read=./data --allow-write=./out --allow-exit
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct Permissions {
+    pub read: Scope,
+    pub write: Scope,
+    /// Environment variables and the current directory
+    pub env: bool,
+    /// The arguments given to the script
+    pub args: bool,
+    /// Ending the program with `exit`
+    pub exit: bool,
+}
+
+impl Permissions {
+    /// Lets the script read the path and everything inside it
+    pub fn allow_read(&mut self, path: &Path) {
+        self.read.add(path);
+    }
+
+    /// Lets the script write to the path and everything inside it
+    pub fn allow_write(&mut self, path: &Path) {
+        self.write.add(path);
+    }
+
+    pub fn can_read(&self, path: &Path) -> bool {
+        self.read.allows(path)
+    }
+
+    pub fn can_write(&self, path: &Path) -> bool {
+        self.write.allows(path)
+    }
+}
+
+/// Makes the path absolute with the links in the part of it that exists followed, and takes the `.` and `..` out of
+/// the rest, so neither `data/../secret` nor a link inside `data` pointing elsewhere can get around an allowed `data`
+/// and paths that don't exist yet still work
+fn absolute(path: &Path) -> PathBuf {
+    let path = match path.is_absolute() {
+        true => path.to_path_buf(),
+        false => std::env::current_dir().unwrap_or_default().join(path),
+    };
+
+    let components: Vec<Component> = path.components().collect();
+    let mut normalized = PathBuf::new();
+    let mut existing = 0;
+    for end in (1..=components.len()).rev() {
+        let prefix: PathBuf = components[..end].iter().collect();
+        if let Ok(canonical) = prefix.canonicalize() {
+            normalized = canonical;
+            existing = end;
+            break;
+        }
+    }
+
+    for component in &components[existing..] {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+
+    normalized
+}
@@ -42,7 +42,14 @@ pub enum Token {
     MutVarDeclaration(Position),   // "set"
     ImmutVarDeclaration(Position), // "const"
     Assignment(Position),          // =
+    Export(Position),              // "pub" or "export"
+    Builtin(Builtin, Position),    // "print", "read_line", ...
     Int(i32, Position),
+    Float(f64, Position),
+    Bool(bool, Position),     // "true" or "false"
+    None(Position),           // "none"
+    Str(String, Position),    // "..." without the quotes
+    RawStr(String, Position), // r"..." without the quotes
     Variable(VariableName, Position),
 }
 
@@ -58,7 +65,14 @@ impl Display for Token {
             Token::MutVarDeclaration(p) => write!(f, "set pos: {}", p),
             Token::ImmutVarDeclaration(p) => write!(f, "const pos: {}", p),
             Token::Assignment(p) => write!(f, "= pos: {}", p),
+            Token::Export(p) => write!(f, "pub pos: {}", p),
+            Token::Builtin(builtin, p) => write!(f, "{} pos: {}", builtin, p),
             Token::Int(value, p) => write!(f, "{} pos: {}", value, p),
+            Token::Float(value, p) => write!(f, "{} pos: {}", value, p),
+            Token::Bool(value, p) => write!(f, "{} pos: {}", value, p),
+            Token::None(p) => write!(f, "none pos: {}", p),
+            Token::Str(value, p) => write!(f, "\"{}\" pos: {}", value, p),
+            Token::RawStr(value, p) => write!(f, "r\"{}\" pos: {}", value, p),
             Token::Variable(name, p) => write!(f, "{} pos: {}", name, p),
         }
     }
@@ -76,12 +90,207 @@ impl Token {
             Token::MutVarDeclaration(pos) => pos.clone(),
             Token::ImmutVarDeclaration(pos) => pos.clone(),
             Token::Assignment(pos) => pos.clone(),
+            Token::Export(pos) => pos.clone(),
+            Token::Builtin(_, pos) => pos.clone(),
             Token::Int(_, pos) => pos.clone(),
+            Token::Float(_, pos) => pos.clone(),
+            Token::Bool(_, pos) => pos.clone(),
+            Token::None(pos) => pos.clone(),
+            Token::Str(_, pos) => pos.clone(),
+            Token::RawStr(_, pos) => pos.clone(),
             Token::Variable(_, pos) => pos.clone(),
         }
     }
 }
 
+/// Functions built into the language, called by name followed by their arguments
+///
+/// EXAMPLE:
+///     "println x + 1"
+///     "set biggest = math.max x y"
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Builtin {
+    Print,
+    PrintLine,
+    Input,
+    ReadLine,
+    ReadAll,
+    ParseInt,
+    ParseFloat,
+    MathAbs,
+    MathMin,
+    MathMax,
+    MathPow,
+    MathSqrt,
+    MathFloor,
+    MathCeil,
+    MathRound,
+    MathSin,
+    MathCos,
+    MathTan,
+    MathAsin,
+    MathAcos,
+    MathAtan,
+    MathLog,
+    MathLog10,
+    MathLog2,
+    MathExp,
+    MathPi,
+    MathE,
+    StrLen,
+    StrUpper,
+    StrLower,
+    StrTrim,
+    StrSplit,
+    StrJoin,
+    StrContains,
+    StrStartsWith,
+    StrReplace,
+    StrFind,
+    StrChars,
+    StrCharAt,
+    StrSubstring,
+    Format,
+    ArraySort,
+    ArrayReverse,
+    ArrayZip,
+    ArrayEnumerate,
+    FsRead,
+    FsWrite,
+    FsAppend,
+    FsExists,
+    FsListDir,
+    EnvGet,
+    EnvHas,
+    EnvArgs,
+    EnvCwd,
+    Exit,
+    JsonParse,
+    JsonStringify,
+    MapGet,
+    MapKeys,
+    CsvParse,
+    CsvParseRecords,
+    CsvStringify,
+    RandomInt,
+    RandomFloat,
+    RandomChoice,
+    RandomShuffle,
+}
+
+/// Every builtin with its name and the least and most arguments it takes
+const BUILTINS: &[(Builtin, &str, usize, usize)] = &[
+    (Builtin::Print, "print", 1, 1),
+    (Builtin::PrintLine, "println", 0, 1),
+    (Builtin::Input, "input", 0, 1),
+    (Builtin::ReadLine, "read_line", 0, 0),
+    (Builtin::ReadAll, "read_all", 0, 0),
+    (Builtin::ParseInt, "parse_int", 1, 1),
+    (Builtin::ParseFloat, "parse_float", 1, 1),
+    (Builtin::MathAbs, "math.abs", 1, 1),
+    (Builtin::MathMin, "math.min", 2, 2),
+    (Builtin::MathMax, "math.max", 2, 2),
+    (Builtin::MathPow, "math.pow", 2, 2),
+    (Builtin::MathSqrt, "math.sqrt", 1, 1),
+    (Builtin::MathFloor, "math.floor", 1, 1),
+    (Builtin::MathCeil, "math.ceil", 1, 1),
+    (Builtin::MathRound, "math.round", 1, 1),
+    (Builtin::MathSin, "math.sin", 1, 1),
+    (Builtin::MathCos, "math.cos", 1, 1),
+    (Builtin::MathTan, "math.tan", 1, 1),
+    (Builtin::MathAsin, "math.asin", 1, 1),
+    (Builtin::MathAcos, "math.acos", 1, 1),
+    (Builtin::MathAtan, "math.atan", 1, 1),
+    (Builtin::MathLog, "math.log", 1, 1),
+    (Builtin::MathLog10, "math.log10", 1, 1),
+    (Builtin::MathLog2, "math.log2", 1, 1),
+    (Builtin::MathExp, "math.exp", 1, 1),
+    (Builtin::MathPi, "math.pi", 0, 0),
+    (Builtin::MathE, "math.e", 0, 0),
+    (Builtin::StrLen, "str.len", 1, 1),
+    (Builtin::StrUpper, "str.upper", 1, 1),
+    (Builtin::StrLower, "str.lower", 1, 1),
+    (Builtin::StrTrim, "str.trim", 1, 1),
+    (Builtin::StrSplit, "str.split", 2, 2),
+    (Builtin::StrJoin, "str.join", 2, 2),
+    (Builtin::StrContains, "str.contains", 2, 2),
+    (Builtin::StrStartsWith, "str.starts_with", 2, 2),
+    (Builtin::StrReplace, "str.replace", 3, 3),
+    (Builtin::StrFind, "str.find", 2, 2),
+    (Builtin::StrChars, "str.chars", 1, 1),
+    (Builtin::StrCharAt, "str.char_at", 2, 2),
+    (Builtin::StrSubstring, "str.substring", 3, 3),
+    // takes every argument left on the line
+    (Builtin::Format, "format", 1, usize::MAX),
+    (Builtin::ArraySort, "array.sort", 1, 1),
+    (Builtin::ArrayReverse, "array.reverse", 1, 1),
+    (Builtin::ArrayZip, "array.zip", 2, 2),
+    (Builtin::ArrayEnumerate, "array.enumerate", 1, 1),
+    (Builtin::FsRead, "fs.read", 1, 1),
+    (Builtin::FsWrite, "fs.write", 2, 2),
+    (Builtin::FsAppend, "fs.append", 2, 2),
+    (Builtin::FsExists, "fs.exists", 1, 1),
+    (Builtin::FsListDir, "fs.list_dir", 1, 1),
+    (Builtin::EnvGet, "env.get", 1, 1),
+    (Builtin::EnvHas, "env.has", 1, 1),
+    (Builtin::EnvArgs, "env.args", 0, 0),
+    (Builtin::EnvCwd, "env.cwd", 0, 0),
+    (Builtin::Exit, "exit", 0, 1),
+    (Builtin::JsonParse, "json.parse", 1, 1),
+    (Builtin::JsonStringify, "json.stringify", 1, 2),
+    (Builtin::MapGet, "map.get", 2, 2),
+    (Builtin::MapKeys, "map.keys", 1, 1),
+    (Builtin::CsvParse, "csv.parse", 1, 2),
+    (Builtin::CsvParseRecords, "csv.parse_records", 1, 2),
+    (Builtin::CsvStringify, "csv.stringify", 1, 2),
+    (Builtin::RandomInt, "random.int", 2, 2),
+    (Builtin::RandomFloat, "random.float", 0, 0),
+    (Builtin::RandomChoice, "random.choice", 1, 1),
+    (Builtin::RandomShuffle, "random.shuffle", 1, 1),
+];
+
+impl Builtin {
+    pub fn from_name(name: &str) -> Option<Builtin> {
+        BUILTINS
+            .iter()
+            .find(|(_, builtin_name, _, _)| *builtin_name == name)
+            .map(|(builtin, _, _, _)| *builtin)
+    }
+
+    fn entry(&self) -> &'static (Builtin, &'static str, usize, usize) {
+        // every builtin has an entry
+        BUILTINS.iter().find(|(builtin, _, _, _)| builtin == self).unwrap()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.entry().1
+    }
+
+    /// The least and the most arguments the builtin takes
+    pub fn arguments(&self) -> (usize, usize) {
+        let (_, _, min, max) = self.entry();
+        (*min, *max)
+    }
+
+    /// Whether the builtin gives back a value, the ones that don't are only run for what they do
+    pub fn has_value(&self) -> bool {
+        !matches!(
+            self,
+            Builtin::Print
+                | Builtin::PrintLine
+                | Builtin::Exit
+                | Builtin::FsWrite
+                | Builtin::FsAppend
+        )
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum DataTypes {
     Str(String),
@@ -89,6 +298,55 @@ pub enum DataTypes {
     Bool(bool),
     Float(f64),
     Char(char),
+    Array(Vec<DataTypes>),
+    /// Keys in the order they were added
+    Map(Vec<(String, DataTypes)>),
+    /// No value, like a JSON `null`
+    None,
+}
+
+impl DataTypes {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DataTypes::Str(_) => "str",
+            DataTypes::Int(_) => "int",
+            DataTypes::Bool(_) => "bool",
+            DataTypes::Float(_) => "float",
+            DataTypes::Char(_) => "char",
+            DataTypes::Array(_) => "array",
+            DataTypes::Map(_) => "map",
+            DataTypes::None => "none",
+        }
+    }
+}
+
+/// How values look to the user, `2` rather than `Int(2)`
+impl Display for DataTypes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataTypes::Str(value) => write!(f, "{}", value),
+            DataTypes::Int(value) => write!(f, "{}", value),
+            DataTypes::Bool(value) => write!(f, "{}", value),
+            // keep the ".0" on whole floats so they can't be mistaken for ints
+            DataTypes::Float(value) if value.fract() == 0.0 && value.is_finite() => {
+                write!(f, "{:.1}", value)
+            }
+            DataTypes::Float(value) => write!(f, "{}", value),
+            DataTypes::Char(value) => write!(f, "{}", value),
+            DataTypes::Array(values) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "[{}]", values.join(", "))
+            }
+            DataTypes::Map(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            DataTypes::None => write!(f, "none"),
+        }
+    }
 }
 #[derive(PartialEq, Debug, Clone)]
 pub struct Variable {
@@ -118,6 +376,15 @@ impl Variable {
         })
     }
 
+    /// Creates a variable from a value that already is one of the `DataTypes`
+    pub fn from_data_type(name: String, value: DataTypes, mutable: bool) -> Variable {
+        Variable {
+            name,
+            value,
+            mutable,
+        }
+    }
+
     pub fn convert_to_data_types<T>(inferred: &T) -> Option<DataTypes>
     where
         T: Any,
@@ -0,0 +1,52 @@
+use crate::processing::lexer;
+use crate::processing::parser;
+use crate::processing::syntax_elements::{DataTypes, Position};
+use super::run;
+
+#[test]
+fn test_sort() {
+    assert_eq!(
+        run("println array.sort str.split \"pear apple fig\" \" \"").unwrap(),
+        "[apple, fig, pear]\n"
+    );
+    assert_eq!(
+        run("println array.sort str.chars \"cab\"").unwrap(),
+        "[a, b, c]\n"
+    );
+}
+
+#[test]
+fn test_reverse() {
+    assert_eq!(
+        run("println str.join array.reverse str.chars \"héllo\" \"\"").unwrap(),
+        "olléh\n"
+    );
+}
+
+#[test]
+fn test_zip_and_enumerate() {
+    assert_eq!(
+        run("set a = str.chars \"ab\"\nset b = str.split \"x,y,z\" \",\"\nprintln array.zip a b")
+            .unwrap(),
+        "[[a, x], [b, y]]\n"
+    );
+    assert_eq!(
+        run("println array.enumerate str.chars \"ab\"").unwrap(),
+        "[[0, a], [1, b]]\n"
+    );
+}
+
+#[test]
+fn test_array_functions_need_arrays() {
+    assert_eq!(
+        run("println array.sort \"abc\""),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnexpectedType(
+                "array",
+                DataTypes::Str("abc".to_string()),
+                Position::new(1, 1)
+            )
+        ))
+    );
+}
@@ -0,0 +1,146 @@
+use std::io::Cursor;
+
+use crate::processing::io::{BufferSink, ReaderSource};
+use crate::processing::lexer::{self, base::BaseLexingReturn};
+use crate::processing::parser::{self, Parser};
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+use super::run_with;
+
+fn run(body: &str) -> String {
+    run_with_input(body, "").unwrap()
+}
+
+fn run_with_input(body: &str, input: &str) -> parser::Result<String> {
+    let mut parser = Parser::default();
+    parser.set_input(Box::new(ReaderSource::new(Cursor::new(input.to_string()))));
+    run_with(parser, body)
+}
+
+#[test]
+fn test_println_expression() {
+    assert_eq!(run("println 1 + 1"), "2\n");
+}
+
+#[test]
+fn test_print_variable() {
+    assert_eq!(run("set x = 4 * 2\nprint x\nprint x"), "88");
+}
+
+#[test]
+fn test_print_returns_empty() {
+    let mut parser = Parser::default();
+    parser.set_output(Box::new(BufferSink::default()));
+    let report = parser.parse("println 3").unwrap();
+
+    assert_eq!(report.statements[0].result, Ok(BaseLexingReturn::Empty));
+}
+
+#[test]
+fn test_print_without_value() {
+    let mut parser = Parser::default();
+    parser.set_output(Box::new(BufferSink::default()));
+
+    assert!(parser.parse("print").is_err());
+}
+
+#[test]
+fn test_println_without_value() {
+    assert_eq!(run("print 1\nprintln\nprintln 2"), "1\n2\n");
+}
+
+#[test]
+fn test_display_data_types() {
+    assert_eq!(DataTypes::Int(2).to_string(), "2");
+    assert_eq!(DataTypes::Str("hi".to_string()).to_string(), "hi");
+    assert_eq!(DataTypes::Bool(true).to_string(), "true");
+    assert_eq!(DataTypes::Float(2.0).to_string(), "2.0");
+    assert_eq!(DataTypes::Float(2.5).to_string(), "2.5");
+    assert_eq!(DataTypes::Char('a').to_string(), "a");
+}
+
+#[test]
+fn test_read_line() {
+    let output = run_with_input(
+        "set name = read_line\nprintln name\nprintln read_line",
+        "ada\r\nbob\n",
+    );
+    assert_eq!(output.unwrap(), "ada\nbob\n");
+}
+
+#[test]
+fn test_read_all() {
+    let output = run_with_input("read_line\nprint read_all", "skip\nrest\nof it\n");
+    assert_eq!(output.unwrap(), "rest\nof it\n");
+}
+
+#[test]
+fn test_input_prints_prompt() {
+    let output = run_with_input("set x = read_line\nset y = input x", "name? \nada\n");
+    assert_eq!(output.unwrap(), "name? ");
+}
+
+#[test]
+fn test_parse_int_and_float() {
+    let output = run_with_input(
+        "set n = parse_int read_line\nprintln n * 2\nprintln parse_float read_line",
+        " 21 \n2.5\n",
+    );
+    assert_eq!(output.unwrap(), "42\n2.5\n");
+}
+
+#[test]
+fn test_parse_int_invalid() {
+    let output = run_with_input("set n = parse_int read_line", "abc\n");
+    assert_eq!(
+        output,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidInt("abc".to_string(), Position::new(1, 3))
+        ))
+    );
+}
+
+#[test]
+fn test_parse_int_of_int() {
+    let output = run_with_input("parse_int 5", "");
+    assert_eq!(
+        output,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnexpectedType("str", DataTypes::Int(5), Position::new(1, 0))
+        ))
+    );
+}
+
+#[test]
+fn test_read_line_end_of_input() {
+    let output = run_with_input("read_line\nread_line", "only\n");
+    assert_eq!(
+        output,
+        Err(parser::Error::Runtime(
+            2,
+            lexer::Error::EndOfInput(Position::new(2, 0))
+        ))
+    );
+}
+
+#[test]
+fn test_assign_print() {
+    let output = run_with_input("set x = print 1", "");
+    assert_eq!(
+        output,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::BuiltinHasNoValue(Builtin::Print, Position::new(1, 3))
+        ))
+    );
+}
+
+#[test]
+fn test_bool_and_none_literals() {
+    assert_eq!(
+        run("set yes = true\nconst nothing = none\nprintln yes\nprintln false\nprintln nothing"),
+        "true\nfalse\nnone\n"
+    );
+    assert_eq!(run("println \"{true} or {none}\""), "true or none\n");
+}
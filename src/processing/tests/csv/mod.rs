@@ -0,0 +1,105 @@
+use crate::processing::lexer;
+use crate::processing::parser;
+use crate::processing::syntax_elements::Position;
+use super::run;
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        run(r#"println csv.parse "name,note\nada,\"likes, commas\"\r\nbob,\"says \"\"hi\"\"\"\n""#)
+            .unwrap(),
+        "[[name, note], [ada, likes, commas], [bob, says \"hi\"]]\n"
+    );
+    assert_eq!(
+        run(r#"println csv.parse "a,\"two\nlines\",c""#).unwrap(),
+        "[[a, two\nlines, c]]\n"
+    );
+    assert_eq!(run(r#"println csv.parse "a,,""#).unwrap(), "[[a, , ]]\n");
+    assert_eq!(run(r#"println csv.parse """#).unwrap(), "[]\n");
+}
+
+#[test]
+fn test_parse_delimiter() {
+    assert_eq!(
+        run(r#"println csv.parse "a;b,c\n1;2" ";""#).unwrap(),
+        "[[a, b,c], [1, 2]]\n"
+    );
+    assert_eq!(
+        run(r#"println csv.parse "a\tb" "\t""#).unwrap(),
+        "[[a, b]]\n"
+    );
+    assert_eq!(
+        run(r#"println csv.parse "a" "ab""#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidDelimiter("ab".to_string(), Position::new(1, 1))
+        ))
+    );
+}
+
+#[test]
+fn test_parse_records() {
+    assert_eq!(
+        run(r#"println csv.parse_records "name,age\nada,36\nbob,7\n""#).unwrap(),
+        "[{name: ada, age: 36}, {name: bob, age: 7}]\n"
+    );
+    assert_eq!(
+        run(r#"println csv.parse_records "name,age""#).unwrap(),
+        "[]\n"
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        run(r#"println csv.parse "a,b\n\"open,c""#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidCsv(
+                "Unterminated quoted field".to_string(),
+                2,
+                Position::new(1, 1)
+            )
+        ))
+    );
+    assert_eq!(
+        run(r#"println csv.parse_records "a,b\n1,2\n3""#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidCsv(
+                "Expected 2 fields like the header but found 1".to_string(),
+                3,
+                Position::new(1, 1)
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_parse_records_duplicate_header() {
+    assert_eq!(
+        run(r#"println csv.parse_records "a,b,a
+1,2,3""#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidCsv(
+                "Header \"a\" is there more than once".to_string(),
+                1,
+                Position::new(1, 1)
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_stringify() {
+    assert_eq!(
+        run(r#"print csv.stringify csv.parse "a,\"b,c\"\n\"say \"\"hi\"\"\",\"x\ny\"""#).unwrap(),
+        "a,\"b,c\"\n\"say \"\"hi\"\"\",\"x\ny\"\n"
+    );
+    assert_eq!(
+        run("set people = csv.parse_records \"name,age\\nada,36\"\nprint csv.stringify people \";\"")
+            .unwrap(),
+        "name;age\nada;36\n"
+    );
+}
@@ -0,0 +1,95 @@
+use super::run;
+use crate::processing::lexer;
+use crate::processing::parser;
+use crate::processing::syntax_elements::{DataTypes, Position};
+
+#[test]
+fn test_tuple() {
+    assert_eq!(run("set (a, b) = (1, 2)\nprintln a + b").unwrap(), "3\n");
+    assert_eq!(
+        run("set x = 4\nconst (a, _, c) = (x * 2, \"skipped, really\", (5, 6))\nprintln a\nprintln c").unwrap(),
+        "8\n[5, 6]\n"
+    );
+}
+
+#[test]
+fn test_array() {
+    let output = run(
+        "set list = str.split \"a b c\" \" \"\nset [first, rest..] = list\nprintln first\nprintln rest\nset [x, y, z] = list\nprintln z",
+    );
+    assert_eq!(output.unwrap(), "a\n[b, c]\nc\n");
+
+    assert_eq!(
+        run("set [only, rest..] = str.split \"a\" \" \"\nprintln rest").unwrap(),
+        "[]\n"
+    );
+}
+
+#[test]
+fn test_struct() {
+    let output = run(
+        "set p = json.parse \"{{\\\"x\\\": 1, \\\"y\\\": 2, \\\"z\\\": 3}}\"\nset Point { x, y } = p\nprintln x + y",
+    );
+    assert_eq!(output.unwrap(), "3\n");
+
+    assert_eq!(
+        run("set p = json.parse \"{{\\\"x\\\": 1}}\"\nset Point { x, y } = p"),
+        Err(parser::Error::Runtime(
+            2,
+            lexer::Error::MissingKey("y".to_string(), Position::new(2, 7))
+        ))
+    );
+}
+
+#[test]
+fn test_wrong_shape() {
+    assert_eq!(
+        run("set (a, b) = (1, 2, 3)"),
+        Err(parser::Error::PatternMismatch(
+            1,
+            "(a, b)".to_string(),
+            "2".to_string(),
+            3
+        ))
+    );
+    assert_eq!(
+        run("set [a, b, rest..] = (1)"),
+        Err(parser::Error::PatternMismatch(
+            1,
+            "[a, b, rest..]".to_string(),
+            "at least 2".to_string(),
+            1
+        ))
+    );
+    assert_eq!(
+        run("set [a, b] = 5"),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnexpectedType("array", DataTypes::Int(5), Position::new(1, 4))
+        ))
+    );
+}
+
+#[test]
+fn test_invalid_pattern() {
+    for line in [
+        "set (a, a) = (1, 2)",
+        "set [rest.., a] = (1, 2)",
+        "set (a b) = (1, 2)",
+        "set (a, b)",
+    ] {
+        assert_eq!(
+            run(line),
+            Err(parser::Error::InvalidPattern(1, line.to_string())),
+            "{}",
+            line
+        );
+    }
+    assert_eq!(
+        run("set a = 1\nset (a, b) = (1, 2)"),
+        Err(parser::Error::Runtime(
+            2,
+            lexer::Error::VariableAlreadyExists("a".to_string())
+        ))
+    );
+}
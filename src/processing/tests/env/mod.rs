@@ -0,0 +1,115 @@
+use crate::processing::lexer::{self, base::BaseLexingReturn};
+use crate::processing::parser::{self, report::ExecutionReport, Parser};
+use crate::processing::permissions::Permissions;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+use super::run_report;
+
+fn all_allowed() -> Permissions {
+    Permissions {
+        env: true,
+        args: true,
+        exit: true,
+        ..Default::default()
+    }
+}
+
+fn run(body: &str, permissions: Permissions) -> parser::Result<(String, ExecutionReport)> {
+    let mut parser = Parser::default();
+    parser.set_permissions(permissions);
+    parser.set_args(vec!["one".to_string(), "two words".to_string()]);
+    run_report(parser, body)
+}
+
+fn output(body: &str, permissions: Permissions) -> parser::Result<String> {
+    run(body, permissions).map(|(output, _)| output)
+}
+
+#[test]
+fn test_args() {
+    assert_eq!(
+        output("println env.args", all_allowed()).unwrap(),
+        "[one, two words]\n"
+    );
+}
+
+#[test]
+fn test_env_vars() {
+    std::env::set_var("PROG_LANG_TEST_ENV", "set");
+
+    assert_eq!(
+        output(
+            "println env.get \"PROG_LANG_TEST_ENV\"\nprintln env.has \"PROG_LANG_TEST_UNSET\"",
+            all_allowed()
+        )
+        .unwrap(),
+        "set\nfalse\n"
+    );
+    assert_eq!(
+        output("println env.get \"PROG_LANG_TEST_UNSET\"", all_allowed()),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::EnvNotSet("PROG_LANG_TEST_UNSET".to_string(), Position::new(1, 1))
+        ))
+    );
+}
+
+#[test]
+fn test_cwd() {
+    let cwd = std::env::current_dir().unwrap().display().to_string();
+    assert_eq!(
+        output("println env.cwd", all_allowed()).unwrap(),
+        format!("{}\n", cwd)
+    );
+}
+
+#[test]
+fn test_not_allowed_by_default() {
+    assert_eq!(
+        output("println env.args", Permissions::default()),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::NotAllowed("env.args", "--allow-args", Position::new(1, 1))
+        ))
+    );
+    assert_eq!(
+        output("println env.cwd", Permissions::default()),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::NotAllowed("env.cwd", "--allow-env", Position::new(1, 1))
+        ))
+    );
+    assert_eq!(
+        output("exit 1", Permissions::default()),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::NotAllowed("exit", "--allow-exit", Position::new(1, 0))
+        ))
+    );
+}
+
+#[test]
+fn test_exit_stops_the_script() {
+    let (output, report) = run("println 1\nexit 3\nprintln 2", all_allowed()).unwrap();
+
+    assert_eq!(output, "1\n");
+    assert_eq!(report.exit_code, Some(3));
+    assert_eq!(report.statements.len(), 2);
+    assert_eq!(report.statements[1].result, Ok(BaseLexingReturn::Exit(3)));
+}
+
+#[test]
+fn test_exit_without_code() {
+    let (_, report) = run("exit", all_allowed()).unwrap();
+    assert_eq!(report.exit_code, Some(0));
+}
+
+#[test]
+fn test_exit_code_out_of_range() {
+    assert_eq!(
+        output("exit 256", all_allowed()),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::OutOfDomain(Builtin::Exit, DataTypes::Int(256), Position::new(1, 0))
+        ))
+    );
+}
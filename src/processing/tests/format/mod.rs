@@ -0,0 +1,115 @@
+use crate::processing::lexer;
+use crate::processing::parser;
+use crate::processing::syntax_elements::Position;
+use super::run;
+
+fn format_error(message: &str, index: usize) -> parser::Result<String> {
+    Err(parser::Error::Runtime(
+        1,
+        lexer::Error::InvalidFormat(message.to_string(), Position::new(1, 1), index),
+    ))
+}
+
+#[test]
+fn test_format_in_order() {
+    assert_eq!(
+        run("println format \"{} + {} = {}\" 1 2 1 + 2").unwrap(),
+        "1 + 2 = 3\n"
+    );
+}
+
+#[test]
+fn test_format_positional_and_named() {
+    assert_eq!(
+        run("set name = \"ada\"\nprintln format \"{1}{0}{1} {name}\" \"-\" \"=\"").unwrap(),
+        "=-= ada\n"
+    );
+}
+
+#[test]
+fn test_format_alignment() {
+    assert_eq!(
+        run("println format \"[{:>5}]\" \"ab\"").unwrap(),
+        "[   ab]\n"
+    );
+    assert_eq!(run("println format \"[{:<5}]\" 12").unwrap(), "[12   ]\n");
+    assert_eq!(
+        run("println format \"[{:*^6}]\" \"ab\"").unwrap(),
+        "[**ab**]\n"
+    );
+    assert_eq!(run("println format \"[{:4}]\" 7").unwrap(), "[   7]\n");
+    assert_eq!(run("println format \"[{:4}]\" \"é\"").unwrap(), "[é   ]\n");
+}
+
+#[test]
+fn test_format_precision() {
+    assert_eq!(
+        run("println format \"{:>8.2}\" 3.14159").unwrap(),
+        "    3.14\n"
+    );
+    assert_eq!(run("println format \"{:.0}\" 2.5").unwrap(), "2\n");
+    assert_eq!(run("println format \"{:.3}\" \"abcdef\"").unwrap(), "abc\n");
+}
+
+#[test]
+fn test_format_sign_and_zeros() {
+    assert_eq!(run("println format \"{:+}\" 5").unwrap(), "+5\n");
+    assert_eq!(run("println format \"{:05}\" -42").unwrap(), "-0042\n");
+    assert_eq!(
+        run("println format \"{:+08.2}\" 1.5").unwrap(),
+        "+0001.50\n"
+    );
+}
+
+#[test]
+fn test_format_radix() {
+    assert_eq!(
+        run("println format \"{:x} {:X} {:b} {:o} {:#>6b}\" 255 255 5 8 5").unwrap(),
+        "ff FF 101 10 ###101\n"
+    );
+}
+
+#[test]
+fn test_format_escaped_braces() {
+    assert_eq!(run("println format \"{{{}}}\\t\" 1").unwrap(), "{1}\t\n");
+}
+
+#[test]
+fn test_format_errors_at_specifier() {
+    assert_eq!(
+        run("println format \"ab {:q}\" 1"),
+        format_error("Invalid format specifier :q", 3)
+    );
+    assert_eq!(
+        run("println format \"{} {}\" 1"),
+        format_error("No argument 1", 3)
+    );
+    assert_eq!(
+        run("println format \"{x}\""),
+        format_error("No variable named x", 0)
+    );
+    assert_eq!(
+        run("println format \"a {:x}\" 1.5"),
+        format_error("Hex, binary and octal only work on ints, not a float", 2)
+    );
+    assert_eq!(
+        run("println format \"{:.2}\" 1"),
+        format_error("Precision only works on floats and strings, not a int", 0)
+    );
+    assert_eq!(
+        run("println format \"ab {:18446744073709551615}\" 1"),
+        format_error("Width 18446744073709551615 is over the limit of 1024", 3)
+    );
+    assert_eq!(
+        run("println format \"{:.5000}\" 1.5"),
+        format_error("Precision 5000 is over the limit of 1024", 0)
+    );
+    assert_eq!(
+        run("println format \"{\" 1"),
+        format_error("Missing } for the {", 0)
+    );
+    assert_eq!(
+        run("println format \"a}\" 1"),
+        format_error("Unmatched }, use }} for a single }", 1)
+    );
+}
@@ -0,0 +1,124 @@
+use std::fs;
+
+use crate::processing::lexer;
+use crate::processing::parser::{self, Parser};
+use crate::processing::permissions::{Permissions, Scope};
+use crate::processing::syntax_elements::Position;
+use crate::temp_dir::TempDir;
+use super::run_with;
+
+/// A directory of its own for the test, empty at the start and removed at the end
+fn directory(test: &str) -> TempDir {
+    TempDir::new(&format!("fs-{}", test))
+}
+
+fn run(body: &str, permissions: Permissions) -> parser::Result<String> {
+    let mut parser = Parser::default();
+    parser.set_permissions(permissions);
+    run_with(parser, body)
+}
+
+#[test]
+fn test_write_then_read() {
+    let directory = directory("write-read");
+    let file = directory.join("notes.txt");
+    let mut permissions = Permissions::default();
+    permissions.allow_read(&directory);
+    permissions.allow_write(&directory);
+
+    let body = format!(
+        "set path = \"{}\"\nfs.write path \"a\"\nfs.append path 1 + 1\nprintln fs.read path\nprintln fs.exists path",
+        file.display()
+    );
+
+    assert_eq!(run(&body, permissions).unwrap(), "a2\ntrue\n");
+}
+
+#[test]
+fn test_list_dir() {
+    let directory = directory("list-dir");
+    fs::write(directory.join("b.txt"), "").unwrap();
+    fs::write(directory.join("a.txt"), "").unwrap();
+    let permissions = Permissions {
+        read: Scope::All,
+        ..Default::default()
+    };
+
+    let body = format!("println fs.list_dir \"{}\"", directory.display());
+    assert_eq!(run(&body, permissions).unwrap(), "[a.txt, b.txt]\n");
+}
+
+#[test]
+fn test_denied_by_default() {
+    let directory = directory("denied");
+    let file = directory.join("secret.txt").display().to_string();
+
+    assert_eq!(
+        run(
+            &format!("println fs.read \"{}\"", file),
+            Permissions::default()
+        ),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::PermissionDenied("read", file, Position::new(1, 1))
+        ))
+    );
+}
+
+#[test]
+fn test_read_does_not_allow_write() {
+    let directory = directory("read-only");
+    let mut permissions = Permissions::default();
+    permissions.allow_read(&directory);
+
+    let body = format!("fs.write \"{}\" \"x\"", directory.join("x.txt").display());
+    assert!(matches!(
+        run(&body, permissions),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::PermissionDenied("write", _, _)
+        ))
+    ));
+}
+
+#[test]
+fn test_parent_dir_stays_inside_allowed() {
+    let directory = directory("escape");
+    let mut permissions = Permissions::default();
+    permissions.allow_read(&directory.join("data"));
+
+    let escaping = directory.join("data").join("..").join("secret.txt");
+    assert!(!permissions.can_read(&escaping));
+    assert!(permissions.can_read(&directory.join("data").join("inner.txt")));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_stays_inside_allowed() {
+    let directory = directory("symlink");
+    fs::create_dir_all(directory.join("data")).unwrap();
+    fs::create_dir_all(directory.join("private")).unwrap();
+    fs::write(directory.join("private").join("secret.txt"), "secret").unwrap();
+    std::os::unix::fs::symlink(
+        directory.join("private"),
+        directory.join("data").join("link"),
+    )
+    .unwrap();
+
+    let mut permissions = Permissions::default();
+    permissions.allow_read(&directory.join("data"));
+
+    let escaping = directory.join("data").join("link").join("secret.txt");
+    assert!(!permissions.can_read(&escaping));
+    assert!(!permissions.can_read(&directory.join("data").join("link").join("new.txt")));
+    assert!(permissions.can_read(&directory.join("data").join("inner.txt")));
+
+    let body = format!("println fs.read \"{}\"", escaping.display());
+    assert!(matches!(
+        run(&body, permissions),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::PermissionDenied("read", _, _)
+        ))
+    ));
+}
@@ -0,0 +1,153 @@
+use crate::processing::lexer;
+use crate::processing::parser;
+use crate::processing::syntax_elements::{DataTypes, Position};
+use super::run;
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        run(r#"println json.parse "{{\"name\": \"ada\", \"tags\": [1, 2.5, true, null]}}""#)
+            .unwrap(),
+        "{name: ada, tags: [1, 2.5, true, none]}\n"
+    );
+    assert_eq!(
+        run(r#"println json.parse "\"\\u00e9\\ud83d\\ude00\"""#).unwrap(),
+        "é😀\n"
+    );
+}
+
+#[test]
+fn test_parse_numbers() {
+    assert_eq!(
+        run(r#"println json.parse "[-7, 3000000000, 1e2]""#).unwrap(),
+        "[-7, 3000000000.0, 100.0]\n"
+    );
+}
+
+#[test]
+fn test_duplicate_keys_keep_the_last() {
+    assert_eq!(
+        run(r#"println json.parse "{{\"a\": 1, \"b\": 2, \"a\": 3}}""#).unwrap(),
+        "{a: 3, b: 2}\n"
+    );
+}
+
+#[test]
+fn test_map_get_and_keys() {
+    assert_eq!(
+        run("set user = json.parse \"{{\\\"name\\\": \\\"ada\\\", \\\"age\\\": 36}}\"\nprintln map.get user \"name\"\nprintln map.keys user")
+            .unwrap(),
+        "ada\n[name, age]\n"
+    );
+    assert_eq!(
+        run("set user = json.parse \"{{}}\"\nprintln map.get user \"name\""),
+        Err(parser::Error::Runtime(
+            2,
+            lexer::Error::MissingKey("name".to_string(), Position::new(2, 1))
+        ))
+    );
+}
+
+#[test]
+fn test_parse_error_position() {
+    assert_eq!(
+        run(r#"println json.parse "{{\"a\": 1,\n  \"b\" 2}}""#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidJson(
+                "Expected ':' but found '2'".to_string(),
+                2,
+                7,
+                Position::new(1, 1)
+            )
+        ))
+    );
+    assert_eq!(
+        run(r#"println json.parse "[1, 2""#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidJson(
+                "Expected ',' or ']' but the text ended".to_string(),
+                1,
+                6,
+                Position::new(1, 1)
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_stringify_literals() {
+    assert_eq!(
+        run("println json.stringify true\nprintln json.stringify none").unwrap(),
+        "true\nnull\n"
+    );
+    assert_eq!(
+        run(r#"println json.stringify str.split "a b" " " false"#).unwrap(),
+        "[\"a\",\"b\"]\n"
+    );
+}
+
+#[test]
+fn test_stringify() {
+    assert_eq!(
+        run(r#"println json.stringify json.parse "{{\"a\": [1, \"x\\ny\"], \"b\": null}}""#)
+            .unwrap(),
+        "{\"a\":[1,\"x\\ny\"],\"b\":null}\n"
+    );
+    assert_eq!(
+        run(r#"println json.stringify json.parse "{{\"a\": [1, {{}}]}}" 2"#).unwrap(),
+        "{\n  \"a\": [\n    1,\n    {}\n  ]\n}\n"
+    );
+    assert_eq!(
+        run(r#"println json.stringify str.split "a b" " " true"#).unwrap(),
+        "[\n  \"a\",\n  \"b\"\n]\n"
+    );
+}
+
+#[test]
+fn test_stringify_indent_errors() {
+    assert_eq!(
+        run(r#"println json.stringify str.split "a b" " " 2000000000"#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::IndentTooBig(2000000000, 16, Position::new(1, 1))
+        ))
+    );
+    assert_eq!(
+        run(r#"println json.stringify str.split "a b" " " -1"#),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnexpectedType(
+                "bool or a non-negative int",
+                DataTypes::Int(-1),
+                Position::new(1, 1)
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_parse_rejects_invalid_numbers() {
+    for number in ["1.", "1e", "1.e5", "1e+", "01", "-", "1.5.2", "1e5e5"] {
+        let result = run(&format!("println json.parse \"{}\"", number));
+        assert_eq!(
+            result,
+            Err(parser::Error::Runtime(
+                1,
+                lexer::Error::InvalidJson(
+                    format!("Invalid number {}", number),
+                    1,
+                    number.len() + 1,
+                    Position::new(1, 1)
+                )
+            )),
+            "{}",
+            number
+        );
+    }
+    assert_eq!(
+        run(r#"println json.parse "[0, -0.5, 1E+2, 2e-1]""#).unwrap(),
+        "[0, -0.5, 100.0, 0.2]\n"
+    );
+}
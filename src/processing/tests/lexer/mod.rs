@@ -145,3 +145,43 @@ fn test_duplicate_variable_declaration() {
         Err(Error::VariableAlreadyExists("x".to_string()))
     );
 }
+
+#[test]
+fn test_variable_declaration_without_assignment() {
+    let mut parser = parser::Parser::default();
+    let base = "set x 8 + 2".to_string();
+    parser.current_line = base.clone();
+    let split = parser.split();
+    let tokenized = parser.tokenize(&split, 0).unwrap();
+    let mut lexer = lexer::Lexer::default();
+    let line = Line::new(tokenized, base, split, 0);
+    let lexerized = lexer.lexerize(line, &mut parser.variables);
+
+    assert_eq!(
+        lexerized,
+        Err(Error::UnableToFindVarAssignment("x".to_string()))
+    );
+    assert!(parser.variables.is_empty());
+}
+
+#[test]
+fn test_slice_variable_dec() {
+    let mut parser = parser::Parser::default();
+    let base = "const total = 8 * 2".to_string();
+    parser.current_line = base.clone();
+    let split = parser.split();
+    let tokenized = parser.tokenize(&split, 0).unwrap();
+    let line = Line::new(tokenized, base, split, 0);
+
+    let sliced = variables::slice_variable_dec(&line).unwrap();
+
+    assert_eq!(variables::get_var_name(&line).unwrap(), "total");
+    assert_eq!(
+        sliced,
+        vec![
+            Token::Int(8, Position::new(0, 3)),
+            Token::Multiply(Position::new(0, 4)),
+            Token::Int(2, Position::new(0, 5)),
+        ]
+    );
+}
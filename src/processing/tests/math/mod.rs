@@ -0,0 +1,138 @@
+use crate::processing::lexer;
+use crate::processing::parser;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position};
+use super::run;
+
+fn runtime_error(error: lexer::Error) -> parser::Result<String> {
+    Err(parser::Error::Runtime(1, error))
+}
+
+#[test]
+fn test_ints_stay_ints() {
+    assert_eq!(run("println math.abs -3").unwrap(), "3\n");
+    assert_eq!(run("println math.max 2 7").unwrap(), "7\n");
+    assert_eq!(run("println math.min 2 -7").unwrap(), "-7\n");
+    assert_eq!(run("println math.pow 2 10").unwrap(), "1024\n");
+}
+
+#[test]
+fn test_floats() {
+    assert_eq!(run("println math.abs -2.5").unwrap(), "2.5\n");
+    assert_eq!(run("println math.max 2 7.5").unwrap(), "7.5\n");
+    assert_eq!(run("println math.pow 2 -1").unwrap(), "0.5\n");
+    assert_eq!(run("println math.sqrt 16").unwrap(), "4.0\n");
+}
+
+#[test]
+fn test_rounding() {
+    assert_eq!(run("println math.floor 2.7").unwrap(), "2\n");
+    assert_eq!(run("println math.ceil 2.2").unwrap(), "3\n");
+    assert_eq!(run("println math.round -2.5").unwrap(), "-3\n");
+}
+
+#[test]
+fn test_trig_and_log() {
+    assert_eq!(run("println math.sin 0").unwrap(), "0.0\n");
+    assert_eq!(run("println math.cos 0").unwrap(), "1.0\n");
+    assert_eq!(run("println math.log10 1000").unwrap(), "3.0\n");
+    assert_eq!(run("println math.log2 8").unwrap(), "3.0\n");
+    assert_eq!(run("println math.log math.e").unwrap(), "1.0\n");
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(
+        run("println math.pi").unwrap(),
+        format!("{}\n", std::f64::consts::PI)
+    );
+    assert_eq!(
+        run("set e = math.e\nprint e").unwrap(),
+        std::f64::consts::E.to_string()
+    );
+}
+
+#[test]
+fn test_nested_arguments() {
+    assert_eq!(
+        run("set x = 3\nprintln math.max x + 1 math.abs -10").unwrap(),
+        "10\n"
+    );
+}
+
+#[test]
+fn test_not_a_number() {
+    assert_eq!(
+        run("println math.sqrt \"4\""),
+        runtime_error(lexer::Error::UnexpectedType(
+            "number",
+            DataTypes::Str("4".to_string()),
+            Position::new(1, 1)
+        ))
+    );
+}
+
+#[test]
+fn test_out_of_domain() {
+    assert_eq!(
+        run("println math.sqrt -1"),
+        runtime_error(lexer::Error::OutOfDomain(
+            Builtin::MathSqrt,
+            DataTypes::Int(-1),
+            Position::new(1, 1)
+        ))
+    );
+    assert!(run("println math.log 0").is_err());
+    assert!(run("println math.acos 2").is_err());
+}
+
+#[test]
+fn test_not_finite() {
+    assert_eq!(
+        run("println math.pow -8 0.5"),
+        runtime_error(lexer::Error::OutOfDomain(
+            Builtin::MathPow,
+            DataTypes::Array(vec![DataTypes::Int(-8), DataTypes::Float(0.5)]),
+            Position::new(1, 1)
+        ))
+    );
+    assert_eq!(
+        run("println math.pow 10 400.5"),
+        runtime_error(lexer::Error::OutOfDomain(
+            Builtin::MathPow,
+            DataTypes::Array(vec![DataTypes::Int(10), DataTypes::Float(400.5)]),
+            Position::new(1, 1)
+        ))
+    );
+    assert_eq!(
+        run("println math.exp 1000"),
+        runtime_error(lexer::Error::OutOfDomain(
+            Builtin::MathExp,
+            DataTypes::Int(1000),
+            Position::new(1, 1)
+        ))
+    );
+    assert_eq!(run("println math.exp 1").unwrap(), "2.718281828459045\n");
+}
+
+#[test]
+fn test_overflow() {
+    assert_eq!(
+        run("println math.pow 2 31"),
+        runtime_error(lexer::Error::Overflow(
+            Builtin::MathPow,
+            Position::new(1, 1)
+        ))
+    );
+}
+
+#[test]
+fn test_missing_arguments() {
+    assert_eq!(
+        run("println math.max 1"),
+        runtime_error(lexer::Error::ExpectedArguments(
+            Builtin::MathMax,
+            2,
+            Position::new(1, 1)
+        ))
+    );
+}
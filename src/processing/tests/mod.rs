@@ -1,3 +1,36 @@
 mod parser; 
 mod lexer; 
-mod infer; 
\ No newline at end of file
+mod infer;
+mod builtins;
+mod strings;
+mod math;
+mod format;
+mod arrays;
+mod modules;
+mod fs;
+mod env;
+mod json;
+mod csv;
+mod random;
+mod destructure;
+
+use crate::processing::io::BufferSink;
+use crate::processing::parser::{report::ExecutionReport, Parser, Result};
+
+/// Runs the body on a default parser, giving back what it printed
+fn run(body: &str) -> Result<String> {
+    run_with(Parser::default(), body)
+}
+
+/// Runs the body on a parser the test already set up, giving back what it printed
+fn run_with(parser: Parser, body: &str) -> Result<String> {
+    run_report(parser, body).map(|(output, _)| output)
+}
+
+/// Runs the body on a parser the test already set up, giving back what it printed along with the report
+fn run_report(mut parser: Parser, body: &str) -> Result<(String, ExecutionReport)> {
+    let output = BufferSink::default();
+    parser.set_output(Box::new(output.clone()));
+    let report = parser.parse(body)?;
+    Ok((output.contents(), report))
+}
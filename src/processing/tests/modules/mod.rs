@@ -0,0 +1,228 @@
+use std::fs;
+
+use crate::processing::lexer;
+use crate::processing::parser::{self, Parser};
+use crate::processing::permissions::Permissions;
+use crate::temp_dir::TempDir;
+use super::run_with;
+
+/// Writes the files to a directory of their own and runs "main.pl" from it
+fn run(test: &str, files: &[(&str, &str)]) -> parser::Result<String> {
+    let directory = TempDir::new(test);
+    directory.write(files);
+    run_from(&directory, "main.pl", Permissions::default())
+}
+
+/// Runs `entry` from the directory with the permissions
+fn run_from(
+    directory: &TempDir,
+    entry: &str,
+    permissions: Permissions,
+) -> parser::Result<String> {
+    let main = directory.join(entry);
+    let mut parser = Parser::default();
+    parser.set_permissions(permissions);
+    parser.set_path(&main);
+    run_with(parser, &fs::read_to_string(&main).unwrap())
+}
+
+#[test]
+fn test_import_namespace() {
+    let output = run(
+        "import-namespace",
+        &[
+            (
+                "main.pl",
+                "import \"util.pl\" as u\nimport util\nprintln u.x + util.x",
+            ),
+            ("util.pl", "println \"running util\"\npub set x = 2"),
+        ],
+    );
+
+    // util only runs once even though it is imported twice
+    assert_eq!(output.unwrap(), "running util\n4\n");
+}
+
+#[test]
+fn test_from_import() {
+    let output = run(
+        "from-import",
+        &[
+            ("main.pl", "from util import x y\nprintln x * y"),
+            ("util.pl", "pub set x = 2\nexport const y = 5"),
+        ],
+    );
+
+    assert_eq!(output.unwrap(), "10\n");
+}
+
+#[test]
+fn test_export_destructured() {
+    let output = run(
+        "export-destructured",
+        &[
+            ("main.pl", "from util import x y\nprintln x * y"),
+            ("util.pl", "pub set (x, y) = (2, 5)"),
+        ],
+    );
+
+    assert_eq!(output.unwrap(), "10\n");
+}
+
+#[test]
+fn test_import_relative_to_importing_file() {
+    let output = run(
+        "import-relative",
+        &[
+            (
+                "main.pl",
+                "import \"lib/shapes.pl\" as shapes\nprintln shapes.sides",
+            ),
+            (
+                "lib/shapes.pl",
+                "from \"consts.pl\" import four\npub set sides = four",
+            ),
+            ("lib/consts.pl", "pub set four = 4"),
+        ],
+    );
+
+    assert_eq!(output.unwrap(), "4\n");
+}
+
+#[test]
+fn test_imports_are_not_exported() {
+    let output = run(
+        "import-not-exported",
+        &[
+            ("main.pl", "from a import b"),
+            ("a.pl", "from c import b"),
+            ("c.pl", "pub set b = 1"),
+        ],
+    );
+
+    assert_eq!(
+        output,
+        Err(parser::Error::NotInModule(
+            1,
+            "b".to_string(),
+            "a.pl".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_import_cycle() {
+    let output = run(
+        "import-cycle",
+        &[
+            ("main.pl", "import a"),
+            ("a.pl", "import b"),
+            ("b.pl", "import \"main.pl\""),
+        ],
+    );
+
+    let error = output.unwrap_err().to_string();
+    assert!(error.contains("Import cycle on line 1"));
+    assert!(
+        error.ends_with("main.pl -> a.pl -> b.pl -> main.pl"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_error_in_module() {
+    let output = run(
+        "import-error",
+        &[("main.pl", "import a"), ("a.pl", "set x = 1\nset x = 2")],
+    );
+
+    match output {
+        Err(parser::Error::InModule(path, error)) => {
+            assert!(path.ends_with("a.pl"));
+            assert_eq!(
+                *error,
+                parser::Error::Runtime(2, lexer::Error::VariableAlreadyExists("x".to_string()))
+            );
+        }
+        other => panic!("expected an error in a.pl, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_import() {
+    assert_eq!(
+        run("import-invalid", &[("main.pl", "import a b")]),
+        Err(parser::Error::InvalidImport(1, "import a b".to_string()))
+    );
+    assert!(matches!(
+        run("import-missing", &[("main.pl", "import missing")]),
+        Err(parser::Error::ImportFailed(1, _, _))
+    ));
+}
+
+#[test]
+fn test_private_member() {
+    let files = [
+        ("main.pl", "from util import secret"),
+        ("util.pl", "pub set shared = 1\nset secret = 2"),
+    ];
+
+    assert_eq!(
+        run("private-from-import", &files),
+        Err(parser::Error::PrivateMember(
+            1,
+            "secret".to_string(),
+            "util.pl".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_private_member_of_namespace() {
+    let files = [
+        (
+            "main.pl",
+            "import util\nprintln util.shared\nprintln util.secret",
+        ),
+        ("util.pl", "pub set shared = 1\nset secret = 2"),
+    ];
+
+    assert_eq!(
+        run("private-namespace", &files),
+        Err(parser::Error::PrivateMember(
+            3,
+            "secret".to_string(),
+            "util.pl".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_pub_needs_declaration() {
+    assert_eq!(
+        run("pub-not-declaration", &[("main.pl", "pub println 1")]),
+        Err(parser::Error::InvalidExport(1))
+    );
+}
+
+#[test]
+fn test_import_outside_needs_read_permission() {
+    let directory = TempDir::new("import-outside");
+    directory.write(&[
+        ("app/main.pl", "import \"../secret.pl\" as s\nprintln s.key"),
+        ("secret.pl", "pub set key = 42"),
+    ]);
+
+    assert!(matches!(
+        run_from(&directory, "app/main.pl", Permissions::default()),
+        Err(parser::Error::ImportNotAllowed(1, path)) if path.ends_with("secret.pl")
+    ));
+
+    let mut permissions = Permissions::default();
+    permissions.allow_read(&directory.join("secret.pl"));
+    assert_eq!(
+        run_from(&directory, "app/main.pl", permissions).unwrap(),
+        "42\n"
+    );
+}
@@ -1,6 +1,7 @@
 use crate::processing::{
-    parser::Parser,
-    syntax_elements::{Position, Token},
+    lexer::{self, base::BaseLexingReturn},
+    parser::{Error, ErrorPolicy, Parser},
+    syntax_elements::{DataTypes, Position, Token, Variable},
 };
 
 #[test]
@@ -19,4 +20,140 @@ fn test_tokenize() {
     ];
 
     assert_eq!(tokenized, success);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_tokenize_literals() {
+    let mut parser = Parser::default();
+    parser.current_line = "true false none".to_string();
+    let split = parser.split();
+    let tokenized = parser.tokenize(&split, 0).unwrap();
+
+    assert_eq!(
+        tokenized,
+        vec![
+            Token::Bool(true, Position::new(0, 0)),
+            Token::Bool(false, Position::new(0, 1)),
+            Token::None(Position::new(0, 2)),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_aborts_on_first_error() {
+    let mut parser = Parser::default();
+    let result = parser.parse("set x = 1 + 1\nset x = 2 + 2\nset y = 3 + 3");
+
+    assert_eq!(
+        result,
+        Err(Error::Runtime(
+            2,
+            lexer::Error::VariableAlreadyExists("x".to_string())
+        ))
+    );
+    // lines count from 1 like in an editor
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Error on line 2: Variable with name x already exists!"
+    );
+    assert_eq!(parser.variables.len(), 1);
+}
+
+#[test]
+fn test_parse_continue_on_error() {
+    let mut parser = Parser::default();
+    parser.set_error_policy(ErrorPolicy::Continue);
+    let result = parser.parse("set x = 1 + 1\nset x = 2 + 2\n\nset y = 3 + 3\n5 -");
+
+    assert_eq!(
+        result,
+        Err(Error::Failures(vec![
+            Error::Runtime(2, lexer::Error::VariableAlreadyExists("x".to_string())),
+            Error::Runtime(5, lexer::Error::UnexpectedIntegerPositions(5)),
+        ]))
+    );
+    assert_eq!(parser.variables.len(), 2);
+}
+
+#[test]
+fn test_execute_reports_each_statement() {
+    let mut parser = Parser::default();
+    let report = parser.execute("set x = 1 + 1\n\nx * 3\nx / y");
+
+    let variable_x = Variable::new::<i32>("x".to_string(), &2, true).unwrap();
+    assert_eq!(report.statements.len(), 3);
+    assert_eq!(
+        report.statements[0].result,
+        Ok(BaseLexingReturn::Variable(variable_x))
+    );
+    assert_eq!(
+        report.statements[0].range,
+        Position::new(1, 0)..Position::new(1, 6)
+    );
+    assert_eq!(report.statements[1].source, "x * 3");
+    assert_eq!(report.statements[1].result, Ok(BaseLexingReturn::Int(6)));
+    assert_eq!(
+        report.statements[2].result,
+        Err(Error::InvalidToken(
+            "y".to_string(),
+            "Line: 4, Char: 2".to_string()
+        ))
+    );
+    assert!(!report.is_success());
+    assert_eq!(report.errors().len(), 1);
+}
+
+#[test]
+fn test_division_by_zero_and_overflow() {
+    let mut parser = Parser::default();
+    assert_eq!(
+        parser.parse("set x = 1 / 0"),
+        Err(Error::Runtime(
+            1,
+            lexer::Error::DivisionByZero(Position::new(1, 4))
+        ))
+    );
+
+    let mut parser = Parser::default();
+    assert_eq!(
+        parser.parse("2147483647 + 1"),
+        Err(Error::Runtime(
+            1,
+            lexer::Error::IntOverflow(2147483647, '+', 1, Position::new(1, 1))
+        ))
+    );
+
+    let mut parser = Parser::default();
+    parser.set_error_policy(ErrorPolicy::Continue);
+    let result = parser.parse("set x = 1 / 0\n-2147483648 * 2\nset y = 2 / 1");
+
+    assert_eq!(
+        result,
+        Err(Error::Failures(vec![
+            Error::Runtime(1, lexer::Error::DivisionByZero(Position::new(1, 4))),
+            Error::Runtime(
+                2,
+                lexer::Error::IntOverflow(-2147483648, '*', 2, Position::new(2, 1))
+            ),
+        ]))
+    );
+    assert_eq!(parser.variables.len(), 1);
+}
+
+#[test]
+fn test_float_operand() {
+    let mut parser = Parser::default();
+    assert_eq!(
+        parser.parse("set x = 2.5\nprintln x + 1"),
+        Err(Error::Runtime(
+            2,
+            lexer::Error::UnexpectedType("int", DataTypes::Float(2.5), Position::new(2, 1))
+        ))
+    );
+
+    let mut parser = Parser::default();
+    assert_eq!(
+        parser.parse("1 * 0.5").unwrap_err().to_string(),
+        "Error on line 1: Expected a int but was given a float at Line: 1, Char: 2"
+    );
+}
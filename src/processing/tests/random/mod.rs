@@ -0,0 +1,63 @@
+use crate::processing::lexer;
+use crate::processing::parser::{self, Parser};
+use crate::processing::syntax_elements::{Builtin, Position};
+use crate::processing::utils::random::Rng;
+use super::run_with;
+
+fn run(body: &str, seed: u64) -> parser::Result<String> {
+    let mut parser = Parser::default();
+    parser.set_seed(seed);
+    run_with(parser, body)
+}
+
+#[test]
+fn test_same_seed_same_numbers() {
+    let body = "println random.int 1 100\nprintln random.float\nprintln random.shuffle str.chars \"abcdef\"";
+    assert_eq!(run(body, 7).unwrap(), run(body, 7).unwrap());
+    assert_ne!(run(body, 7).unwrap(), run(body, 8).unwrap());
+}
+
+#[test]
+fn test_int_stays_in_range() {
+    let mut rng = Rng::new(1);
+    for _ in 0..1000 {
+        assert!(rng.below(6) < 6);
+        let float = rng.next_float();
+        assert!((0.0..1.0).contains(&float));
+    }
+
+    assert_eq!(run("println random.int 3 3", 1).unwrap(), "3\n");
+    let value: i32 = run("println random.int -5 5", 1)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    assert!((-5..=5).contains(&value));
+}
+
+#[test]
+fn test_choice_and_shuffle() {
+    let choice = run("println random.choice str.split \"a b c\" \" \"", 3).unwrap();
+    assert!(["a\n", "b\n", "c\n"].contains(&choice.as_str()));
+
+    let shuffled = run("println array.sort random.shuffle str.chars \"hello\"", 3).unwrap();
+    assert_eq!(shuffled, "[e, h, l, l, o]\n");
+}
+
+#[test]
+fn test_random_errors() {
+    assert_eq!(
+        run("println random.int 5 1", 1),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidRange(5, 1, Position::new(1, 1))
+        ))
+    );
+    assert_eq!(
+        run("println random.choice str.chars \"\"", 1),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::EmptyArray(Builtin::RandomChoice, Position::new(1, 1))
+        ))
+    );
+}
@@ -0,0 +1,354 @@
+use crate::processing::io::BufferSink;
+use crate::processing::lexer::{self, base::BaseLexingReturn};
+use crate::processing::parser::{self, ErrorPolicy, Parser};
+use crate::processing::permissions::Permissions;
+use crate::processing::syntax_elements::{Builtin, DataTypes, Position, Token, Variable};
+use super::run;
+
+#[test]
+fn test_split_keeps_strings_together() {
+    let split = parser::split("set s = \"a  b\"  x");
+    assert_eq!(split, vec!["set", "s", "=", "\"a  b\"", "x"]);
+}
+
+#[test]
+fn test_tokenize_string() {
+    let split = parser::split("println \"hello world\"");
+    let tokenized = parser::tokenize(&split, 0, &[]).unwrap();
+    assert_eq!(
+        tokenized[1],
+        Token::Str("hello world".to_string(), Position::new(0, 1))
+    );
+}
+
+#[test]
+fn test_unterminated_string() {
+    let result = run("println \"hello world");
+    assert_eq!(
+        result,
+        Err(parser::Error::UnterminatedString(
+            "\"hello world".to_string(),
+            Position::new(1, 1).to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_declare_string() {
+    let mut parser = Parser::default();
+    let report = parser.parse("set s = \"hi there\"").unwrap();
+    let variable = Variable::new("s".to_string(), &"hi there".to_string(), true).unwrap();
+
+    assert_eq!(
+        report.statements[0].result,
+        Ok(BaseLexingReturn::Variable(variable))
+    );
+}
+
+#[test]
+fn test_declare_single_value() {
+    let output = run("set x = 5\nset y = x\nprintln y");
+    assert_eq!(output.unwrap(), "5\n");
+}
+
+#[test]
+fn test_interpolation() {
+    let output = run("set x = 2\nset a = 3\nprintln \"x = {x}, sum = {a + x}\"");
+    assert_eq!(output.unwrap(), "x = 2, sum = 5\n");
+}
+
+#[test]
+fn test_interpolation_of_strings() {
+    let output = run("set name = \"ada\"\nset greeting = \"hi {name}!\"\nprintln greeting");
+    assert_eq!(output.unwrap(), "hi ada!\n");
+}
+
+#[test]
+fn test_interpolation_calls_builtins() {
+    let output = run(
+        "set x = -3\nset name = \"ab\"\nset s = \"abs = {math.abs x}\"\nprintln s\nprintln \"{str.upper name} {math.max x 2}\"",
+    );
+    assert_eq!(output.unwrap(), "abs = 3\nAB 2\n");
+
+    assert_eq!(
+        run("println \"{print 1}\""),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InInterpolation(
+                Position::new(1, 1),
+                1,
+                Box::new(lexer::Error::BuiltinHasNoValue(
+                    Builtin::Print,
+                    Position::new(1, 0)
+                ))
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_interpolation_checks_for_a_value_first() {
+    let mut parser = Parser::default();
+    parser.set_permissions(Permissions {
+        exit: true,
+        ..Default::default()
+    });
+    parser.set_error_policy(ErrorPolicy::Continue);
+    let output = BufferSink::default();
+    parser.set_output(Box::new(output.clone()));
+
+    let report = parser.execute("println \"{print 1}\"\nset s = \"{exit 3}\"\nprintln 2");
+
+    // neither print nor exit ran before failing
+    assert_eq!(output.contents(), "2\n");
+    assert_eq!(report.exit_code, None);
+    assert_eq!(report.errors().len(), 2);
+}
+
+#[test]
+fn test_interpolation_escaped_braces() {
+    let output = run("set x = 1\nprintln \"{{x}} = {x}\"");
+    assert_eq!(output.unwrap(), "{x} = 1\n");
+}
+
+#[test]
+fn test_interpolation_error_position() {
+    let result = run("set x = 1\nprintln \"x = {x}, y = {y}\"");
+    assert_eq!(
+        result,
+        Err(parser::Error::Runtime(
+            2,
+            lexer::Error::InInterpolation(
+                Position::new(2, 1),
+                14,
+                Box::new(lexer::Error::ExpectedToFindVarValue("y".to_string()))
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_interpolation_inner_error() {
+    let result = run("println \"{1 +}\"");
+    assert_eq!(
+        result,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InInterpolation(
+                Position::new(1, 1),
+                1,
+                Box::new(lexer::Error::UnexpectedIntegerPositions(1))
+            )
+        ))
+    );
+}
+
+#[test]
+fn test_unclosed_interpolation() {
+    let result = run("println \"a {b\"");
+    assert_eq!(
+        result,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnclosedInterpolation(Position::new(1, 1), 2)
+        ))
+    );
+}
+
+#[test]
+fn test_unmatched_brace() {
+    let result = run("println \"a } b\"");
+    assert_eq!(
+        result,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnmatchedBrace(Position::new(1, 1), 2)
+        ))
+    );
+}
+
+#[test]
+fn test_string_value() {
+    let mut parser = Parser::default();
+    let report = parser.parse("\"plain\"").unwrap();
+    assert_eq!(
+        report.statements[0].result,
+        Ok(BaseLexingReturn::Value(DataTypes::Str("plain".to_string())))
+    );
+}
+
+#[test]
+fn test_escapes() {
+    let output = run(r#"println "a\tb\n\"c\" \\ \u{1F600}""#);
+    assert_eq!(output.unwrap(), "a\tb\n\"c\" \\ \u{1F600}\n");
+}
+
+#[test]
+fn test_escaped_quote_keeps_string_together() {
+    let split = parser::split(r#"println "say \"hi there\"" x"#);
+    assert_eq!(split, vec!["println", r#""say \"hi there\"""#, "x"]);
+}
+
+#[test]
+fn test_invalid_escape() {
+    let result = run(r#"println "a\qb""#);
+    assert_eq!(
+        result,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidEscape("\\q".to_string(), Position::new(1, 1), 1)
+        ))
+    );
+}
+
+#[test]
+fn test_invalid_unicode_escape() {
+    let result = run(r#"println "\u{110000}""#);
+    assert_eq!(
+        result,
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::InvalidEscape("\\u{110000}".to_string(), Position::new(1, 1), 0)
+        ))
+    );
+}
+
+#[test]
+fn test_raw_string() {
+    let output = run(r#"set x = 1
+println r"C:\new {x}""#);
+    assert_eq!(output.unwrap(), "C:\\new {x}\n");
+}
+
+#[test]
+fn test_multi_line_string() {
+    let mut parser = Parser::default();
+    let report = parser
+        .parse("set s = \"first\n  second {1 + 1}\n\"\nset after = 1")
+        .unwrap();
+
+    let variable = Variable::new("s".to_string(), &"first\n  second 2\n".to_string(), true);
+    assert_eq!(report.statements.len(), 2);
+    assert_eq!(
+        report.statements[0].result,
+        Ok(BaseLexingReturn::Variable(variable.unwrap()))
+    );
+    assert_eq!(
+        report.statements[0].range,
+        Position::new(1, 0)..Position::new(3, 1)
+    );
+    // the line after the string keeps its own number
+    assert_eq!(report.statements[1].range.start, Position::new(4, 0));
+}
+
+#[test]
+fn test_multi_line_string_range() {
+    let mut parser = Parser::default();
+    let report = parser
+        .parse("println str.replace \"a\nb c\" \"c\" \"d\"\nprintln 1")
+        .unwrap();
+
+    // the string and the two words after it end on line 2
+    assert_eq!(
+        report.statements[0].range,
+        Position::new(1, 0)..Position::new(2, 3)
+    );
+    assert_eq!(
+        report.statements[1].range,
+        Position::new(3, 0)..Position::new(3, 2)
+    );
+}
+
+#[test]
+fn test_unterminated_multi_line_string() {
+    let result = run("set x = 1\nset s = \"never\nclosed");
+    assert_eq!(
+        result,
+        Err(parser::Error::UnterminatedString(
+            "\"never\nclosed".to_string(),
+            Position::new(2, 3).to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_str_len_counts_chars() {
+    assert_eq!(run("println str.len \"héllo\"").unwrap(), "5\n");
+    assert_eq!(run("println str.len \"\"").unwrap(), "0\n");
+}
+
+#[test]
+fn test_str_case_and_trim() {
+    assert_eq!(run("println str.upper \"straße\"").unwrap(), "STRASSE\n");
+    assert_eq!(run("println str.lower \"ÀB\"").unwrap(), "àb\n");
+    assert_eq!(run("println str.trim \"  a b \"").unwrap(), "a b\n");
+}
+
+#[test]
+fn test_str_split_and_join() {
+    assert_eq!(
+        run("set parts = str.split \"a,b,c\" \",\"\nprintln parts").unwrap(),
+        "[a, b, c]\n"
+    );
+    assert_eq!(
+        run("set parts = str.split \"a,b\" \",\"\nprintln str.join parts \" - \"").unwrap(),
+        "a - b\n"
+    );
+    assert_eq!(
+        run("println str.split \"ab\" \"\""),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::EmptySeparator(Position::new(1, 1))
+        ))
+    );
+}
+
+#[test]
+fn test_str_search() {
+    assert_eq!(
+        run("println str.contains \"hello\" \"ell\"").unwrap(),
+        "true\n"
+    );
+    assert_eq!(
+        run("println str.starts_with \"hello\" \"lo\"").unwrap(),
+        "false\n"
+    );
+    assert_eq!(run("println str.find \"héllo\" \"l\"").unwrap(), "2\n");
+    assert_eq!(run("println str.find \"hello\" \"z\"").unwrap(), "-1\n");
+    assert_eq!(
+        run("println str.replace \"a-b-c\" \"-\" \"+\"").unwrap(),
+        "a+b+c\n"
+    );
+}
+
+#[test]
+fn test_str_chars() {
+    assert_eq!(run("println str.chars \"hé\"").unwrap(), "[h, é]\n");
+    assert_eq!(run("println str.char_at \"héllo\" 1").unwrap(), "é\n");
+    assert_eq!(run("println str.substring \"héllo\" 1 3").unwrap(), "él\n");
+    assert_eq!(run("println str.substring \"héllo\" 5 5").unwrap(), "\n");
+}
+
+#[test]
+fn test_str_index_out_of_range() {
+    assert_eq!(
+        run("println str.char_at \"abc\" 3"),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::IndexOutOfRange(3, 3, Position::new(1, 1))
+        ))
+    );
+    assert!(run("println str.substring \"abc\" -1 2").is_err());
+    assert!(run("println str.substring \"abc\" 2 1").is_err());
+}
+
+#[test]
+fn test_str_functions_need_strings() {
+    assert_eq!(
+        run("println str.upper 5"),
+        Err(parser::Error::Runtime(
+            1,
+            lexer::Error::UnexpectedType("str", DataTypes::Int(5), Position::new(1, 1))
+        ))
+    );
+}
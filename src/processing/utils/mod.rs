@@ -1 +1,2 @@
+pub mod random;
 pub mod variables; 
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Pseudo random numbers from SplitMix64, small enough to live in the crate and the same on every platform,
+/// so a seed always gives back the same numbers
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Default for Rng {
+    /// Seeded from the clock, so every run is different unless a seed is set
+    fn default() -> Rng {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or_default();
+        Rng::new(nanos ^ ((std::process::id() as u64) << 32))
+    }
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A float from 0 up to but not including 1
+    pub fn next_float(&mut self) -> f64 {
+        // the top 53 bits are as many as a f64 can hold exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A number from 0 up to but not including `bound`, which can't be 0
+    ///
+    /// Numbers from the top of the range that would make some results more likely than others are thrown away
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Shuffles the items in place, every order being as likely as the others
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            let other = self.below(index as u64 + 1) as usize;
+            items.swap(index, other);
+        }
+    }
+}
@@ -8,21 +8,24 @@ use crate::processing::{
 
 pub fn look_variable(
     var_name: &str,
-    variables: &Vec<Variable>,
+    variables: &[Variable],
     expected_type: DataTypes,
 ) -> Option<Variable> {
     for variable in variables {
         if var_name != variable.name {
             continue;
         }
-        let same_type = match (&variable.value, &expected_type) {
-            (DataTypes::Int(_), DataTypes::Int(_)) => true,
-            (DataTypes::Char(_), DataTypes::Char(_)) => true,
-            (DataTypes::Bool(_), DataTypes::Bool(_)) => true,
-            (DataTypes::Float(_), DataTypes::Float(_)) => true,
-            (DataTypes::Str(_), DataTypes::Str(_)) => true,
-            _ => false,
-        };
+        let same_type = matches!(
+            (&variable.value, &expected_type),
+            (DataTypes::Int(_), DataTypes::Int(_))
+                | (DataTypes::Char(_), DataTypes::Char(_))
+                | (DataTypes::Bool(_), DataTypes::Bool(_))
+                | (DataTypes::Float(_), DataTypes::Float(_))
+                | (DataTypes::Str(_), DataTypes::Str(_))
+                | (DataTypes::Array(_), DataTypes::Array(_))
+                | (DataTypes::Map(_), DataTypes::Map(_))
+                | (DataTypes::None, DataTypes::None)
+        );
 
         if same_type {
             return Some(variable.clone());
@@ -32,30 +35,60 @@ pub fn look_variable(
     None
 }
 
-pub fn var_exists(variables: &Vec<Variable>, name: &str) -> bool {
+pub fn var_exists(variables: &[Variable], name: &str) -> bool {
     for variable in variables {
-        if variable.name == name.to_string() {
-            return true 
+        if variable.name == name {
+            return true;
         }
     }
 
     false
 }
 
-pub fn get_var_name(line: &Line) -> Result<String> {
-    let variable_declaration_position = line.tokenized_body.iter().position(|token| {
+/// Finds the positions of the declaration keyword ("set" or "const") and of the "=" that follows it
+///
+/// EXAMPLE:
+///     "set x = 1 + 1"
+///     (0, 2)
+fn find_declaration_bounds(line: &Line) -> Result<(usize, usize)> {
+    let body = &line.tokenized_body;
+    let declaration_position = body.iter().position(|token| {
         matches!(
             token,
             Token::MutVarDeclaration(_) | Token::ImmutVarDeclaration(_)
         )
     });
 
-    if variable_declaration_position.is_none() {
-        return Err(Error::UnableToFindVarName);
+    let declaration_position = match declaration_position {
+        Some(position) => position,
+        None => return Err(Error::UnableToFindVarName),
+    };
+
+    let assignment_position = body[declaration_position..]
+        .iter()
+        .position(|token| matches!(token, Token::Assignment(_)));
+
+    match assignment_position {
+        Some(offset) => Ok((declaration_position, declaration_position + offset)),
+        None => {
+            let name = match body.get(declaration_position + 1) {
+                Some(Token::Variable(name, _)) => name.to_string(),
+                _ => String::new(),
+            };
+            Err(Error::UnableToFindVarAssignment(name))
+        }
     }
+}
 
-    let variable_name_position = variable_declaration_position.unwrap() + 1;
-    let variable_name_token = &line.tokenized_body[variable_name_position];
+pub fn get_var_name(line: &Line) -> Result<String> {
+    let (declaration_position, assignment_position) = find_declaration_bounds(line)?;
+    let target = &line.tokenized_body[declaration_position + 1..assignment_position];
+
+    let variable_name_token = match target {
+        [] => return Err(Error::UnableToFindVarName),
+        [token] => token,
+        [_, unexpected, ..] => return Err(Error::ExpectedVarName(unexpected.clone())),
+    };
 
     let variable_name = match variable_name_token {
         Token::Variable(name, _) => name,
@@ -66,20 +99,31 @@ pub fn get_var_name(line: &Line) -> Result<String> {
 }
 
 pub fn is_var_mutable(line: &Line) -> bool {
-    match line.tokenized_body[0] {
-        Token::ImmutVarDeclaration(_) => true,
-        Token::MutVarDeclaration(_) => true,
-        _ => false,
-    }
+    matches!(
+        line.tokenized_body[0],
+        Token::ImmutVarDeclaration(_) | Token::MutVarDeclaration(_)
+    )
 }
 
-/// Slices out the variable declaration out the line
-pub fn slice_variable_dec(line: &Line) -> LineTokenizedBody {
-    let mut new = line.tokenized_body.clone();
+/// Creates the variable the line declares, holding `value`
+pub fn declare_variable(line: &Line, variables: &[Variable], value: DataTypes) -> Result<Variable> {
+    let name = get_var_name(line)?;
+    let mutable = is_var_mutable(line);
 
-    for i in (0..3).rev() {
-        new.remove(i);
+    if var_exists(variables, &name) {
+        return Err(Error::VariableAlreadyExists(name));
     }
 
-    new
+    Ok(Variable::from_data_type(name, value, mutable))
+}
+
+/// Slices out the variable declaration out the line, everything up to and including the "="
+///
+/// EXAMPLE:
+///     "set x = 1 + 1"
+///     ["1", "+", "1"]
+pub fn slice_variable_dec(line: &Line) -> Result<LineTokenizedBody> {
+    let (_, assignment_position) = find_declaration_bounds(line)?;
+
+    Ok(line.tokenized_body[assignment_position + 1..].to_vec())
 }
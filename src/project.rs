@@ -0,0 +1,291 @@
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_NAME: &str = "project.toml";
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("No {} in {0}", MANIFEST_NAME)]
+    ManifestNotFound(PathBuf),
+
+    #[error("Unable to read {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    // 0 manifest path
+    // 1 line number, starting at 1 like an editor shows it
+    #[error("{0}, line {1}: {2}")]
+    Syntax(PathBuf, usize, String),
+
+    #[error("{0} is missing {1}")]
+    MissingKey(PathBuf, &'static str),
+
+    #[error("Dependency cycle: {0}")]
+    DependencyCycle(String),
+}
+
+/// A local project another one depends on, imported by its name
+///
+/// EXAMPLE:
+///     "utils = { path = \"../utils\" }"
+///     `import utils` runs the entry of ../utils
+#[derive(PartialEq, Debug, Clone)]
+pub struct Dependency {
+    pub name: String,
+    pub project: Project,
+}
+
+/// What a `project.toml` declares
+///
+/// EXAMPLE:
+///     [project]
+///     name = "demo"
+///     entry = "src/main.pl"
+///     sources = ["src", "lib"]
+///
+///     [dependencies]
+///     utils = { path = "../utils" }
+#[derive(PartialEq, Debug, Clone)]
+pub struct Project {
+    pub name: String,
+    pub root: PathBuf,
+    /// The file `run` starts from
+    pub entry: PathBuf,
+    /// Directories imports are looked for in after the directory of the importing file
+    pub sources: Vec<PathBuf>,
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Project {
+    /// Reads the manifest at the root of the project along with the manifests of its dependencies
+    pub fn load(root: &Path) -> Result<Project> {
+        Project::load_with_parents(root, &mut Vec::new())
+    }
+
+    fn load_with_parents(root: &Path, parents: &mut Vec<PathBuf>) -> Result<Project> {
+        let root = root
+            .canonicalize()
+            .map_err(|e| Error::Io(root.to_path_buf(), e))?;
+        let manifest = root.join(MANIFEST_NAME);
+        if !manifest.exists() {
+            return Err(Error::ManifestNotFound(root));
+        }
+
+        if parents.contains(&root) {
+            let mut chain: Vec<String> = parents.iter().map(|p| p.display().to_string()).collect();
+            chain.push(root.display().to_string());
+            return Err(Error::DependencyCycle(chain.join(" -> ")));
+        }
+
+        let text =
+            std::fs::read_to_string(&manifest).map_err(|e| Error::Io(manifest.clone(), e))?;
+        let parsed = parse(&text)
+            .map_err(|(line, message)| Error::Syntax(manifest.clone(), line, message))?;
+
+        let find = |section: &str, key: &str| {
+            parsed
+                .iter()
+                .find(|entry| entry.section == section && entry.key == key)
+                .map(|entry| &entry.value)
+        };
+
+        let name = match find("project", "name") {
+            Some(Value::Str(name)) => name.to_string(),
+            _ => return Err(Error::MissingKey(manifest, "name")),
+        };
+        let entry = match find("project", "entry") {
+            Some(Value::Str(entry)) => root.join(entry),
+            _ => return Err(Error::MissingKey(manifest, "entry")),
+        };
+        let sources = match find("project", "sources") {
+            Some(Value::List(sources)) => sources.iter().map(|source| root.join(source)).collect(),
+            _ => Vec::new(),
+        };
+
+        parents.push(root.clone());
+        let mut dependencies = Vec::new();
+        for entry in parsed
+            .iter()
+            .filter(|entry| entry.section == "dependencies")
+        {
+            let path = match &entry.value {
+                Value::Str(path) | Value::Path(path) => root.join(path),
+                Value::List(_) => {
+                    let message = format!("Expected a path for the dependency {}", entry.key);
+                    return Err(Error::Syntax(manifest, entry.line, message));
+                }
+            };
+
+            dependencies.push(Dependency {
+                name: entry.key.to_string(),
+                project: Project::load_with_parents(&path, parents)?,
+            });
+        }
+        parents.pop();
+
+        Ok(Project {
+            name,
+            root,
+            entry,
+            sources,
+            dependencies,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Value {
+    Str(String),
+    List(Vec<String>),
+    /// `{ path = "..." }`
+    Path(String),
+}
+
+#[derive(PartialEq, Debug)]
+struct Entry {
+    section: String,
+    key: String,
+    value: Value,
+    line: usize,
+}
+
+/// Reads the small part of TOML a manifest needs: sections, and keys set to a string,
+/// a list of strings or `{ path = "..." }`
+///
+/// The error is the line it is on along with what is wrong
+fn parse(text: &str) -> std::result::Result<Vec<Entry>, (usize, String)> {
+    let mut entries = Vec::new();
+    let mut section = String::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => return Err((line_number, format!("Expected key = value, found {}", line))),
+        };
+
+        let value =
+            read_value(value).ok_or_else(|| (line_number, format!("Invalid value {}", value)))?;
+        entries.push(Entry {
+            section: section.clone(),
+            key: key.to_string(),
+            value,
+            line: line_number,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn read_value(value: &str) -> Option<Value> {
+    if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        let items = list
+            .split(',')
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .map(read_str)
+            .collect::<Option<Vec<String>>>()?;
+        return Some(Value::List(items));
+    }
+
+    if let Some(table) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+        let (key, path) = table.split_once('=')?;
+        if key.trim() != "path" {
+            return None;
+        }
+        return Some(Value::Path(read_str(path.trim())?));
+    }
+
+    read_str(value).map(Value::Str)
+}
+
+fn read_str(value: &str) -> Option<String> {
+    let contents = value.strip_prefix('"')?.strip_suffix('"')?;
+    Some(contents.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+
+    #[test]
+    fn test_parse() {
+        let entries = parse(
+            "# demo\n[project]\nname = \"demo\"\nsources = [\"src\", \"lib\"]\n\n[dependencies]\nutils = { path = \"../utils\" }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                Entry {
+                    section: "project".to_string(),
+                    key: "name".to_string(),
+                    value: Value::Str("demo".to_string()),
+                    line: 3,
+                },
+                Entry {
+                    section: "project".to_string(),
+                    key: "sources".to_string(),
+                    value: Value::List(vec!["src".to_string(), "lib".to_string()]),
+                    line: 4,
+                },
+                Entry {
+                    section: "dependencies".to_string(),
+                    key: "utils".to_string(),
+                    value: Value::Path("../utils".to_string()),
+                    line: 7,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_line() {
+        assert_eq!(
+            parse("[project]\nname = demo"),
+            Err((2, "Invalid value demo".to_string()))
+        );
+        assert!(parse("[project]\nname").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_entry() {
+        let root = TempDir::new("project-missing-entry");
+        root.write(&[(MANIFEST_NAME, "[project]\nname = \"x\"")]);
+
+        assert!(matches!(
+            Project::load(&root),
+            Err(Error::MissingKey(_, "entry"))
+        ));
+    }
+
+    #[test]
+    fn test_load_dependency_cycle() {
+        let base = TempDir::new("project-cycle");
+        for (name, other) in [("a", "b"), ("b", "a")] {
+            let manifest = format!(
+                "[project]\nname = \"{}\"\nentry = \"main.pl\"\n[dependencies]\n{} = {{ path = \"../{}\" }}",
+                name, other, other
+            );
+            base.write(&[(&format!("{}/{}", name, MANIFEST_NAME), &manifest)]);
+        }
+
+        assert!(matches!(
+            Project::load(&base.join("a")),
+            Err(Error::DependencyCycle(_))
+        ));
+    }
+}
@@ -1,156 +1,489 @@
-use std::{env, io::Read, path::PathBuf};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
 
-use crate::processing::parser::Parser;
+use crate::processing::parser::{self, ErrorPolicy, Parser};
+use crate::processing::permissions::{Permissions, Scope};
+use crate::project::{self, Project, MANIFEST_NAME};
 
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Expected a file path!")]
+    ExpectedFilePath,
+
+    #[error("Unexpected arguments! {0:?}")]
+    UnexpectedArguments(Vec<String>),
+
+    #[error("Invalid seed {0:?}, expected a whole number from 0 to {}", u64::MAX)]
+    InvalidSeed(String),
+
+    #[error("File does not exist at this path!")]
+    FileDoesNotExist,
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Parser(#[from] parser::Error),
+
+    #[error(transparent)]
+    Project(#[from] project::Error),
+}
+
+#[derive(Default)]
 pub struct Arguments {
     pub program_name: String,
     pub file: PathBuf,
+    pub error_policy: ErrorPolicy,
+    pub permissions: Permissions,
+    /// Everything after the file, handed to the script
+    pub script_args: Vec<String>,
+    /// Seeds the `random` builtins so runs can be repeated
+    pub seed: Option<u64>,
 }
 
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Default)]
 pub struct CLI {
     pub args: Arguments,
 }
 
 impl CLI {
-    pub fn get_args(&mut self) -> () {
-        let args: Vec<String> = env::args().collect();
+    /// Reads the command line, expected as `program [flags] file [script args]`
+    ///
+    /// The flags are `--continue-on-error`, `--allow-read[=paths]`, `--allow-write[=paths]`, `--allow-env`,
+    /// `--allow-args`, `--allow-exit` and `--seed=number`. The file can also be the root directory of a project, which is the default
+    /// when there is a manifest in the current directory
+    pub fn get_args(&mut self, args: Vec<String>) -> Result<()> {
+        let mut args = args.into_iter();
+        let program_name = args.next().unwrap_or_default();
+        let mut error_policy = ErrorPolicy::default();
+        let mut permissions = Permissions::default();
+        let mut file = None;
+        let mut unexpected = Vec::new();
+        let mut script_args = Vec::new();
+        let mut seed = None;
+
+        for arg in args {
+            match arg.as_str() {
+                _ if file.is_some() => script_args.push(arg),
+                "--continue-on-error" => error_policy = ErrorPolicy::Continue,
+                "--allow-read" => permissions.read = Scope::All,
+                "--allow-write" => permissions.write = Scope::All,
+                "--allow-env" => permissions.env = true,
+                "--allow-args" => permissions.args = true,
+                "--allow-exit" => permissions.exit = true,
+                _ if arg.starts_with("--allow-read=") => {
+                    for path in paths(&arg) {
+                        permissions.allow_read(path);
+                    }
+                }
+                _ if arg.starts_with("--allow-write=") => {
+                    for path in paths(&arg) {
+                        permissions.allow_write(path);
+                    }
+                }
+                _ if arg.starts_with("--seed=") => {
+                    let (_, number) = arg.split_once('=').unwrap_or_default();
+                    match number.parse::<u64>() {
+                        Ok(number) => seed = Some(number),
+                        Err(_) => return Err(Error::InvalidSeed(number.to_string())),
+                    }
+                }
+                _ if arg.starts_with("--") => unexpected.push(arg),
+                _ => file = Some(PathBuf::from(arg)),
+            }
+        }
 
-        if args.len() > 1 {
-            println!("Unexpected arguments!")
-        } else if args.len() == 0 {
-            println!("Expected a file path!")
+        if !unexpected.is_empty() {
+            return Err(Error::UnexpectedArguments(unexpected));
         }
 
-        let arguments = self.args = Arguments {
-            program_name: args[0].clone(),
-            file: PathBuf::from(args[1].clone()),
+        let file = match file {
+            Some(file) => file,
+            None if Path::new(MANIFEST_NAME).exists() => PathBuf::from("."),
+            None => return Err(Error::ExpectedFilePath),
+        };
+
+        self.args = Arguments {
+            program_name,
+            file,
+            error_policy,
+            permissions,
+            script_args,
+            seed,
         };
+
+        Ok(())
     }
 
     pub fn file_exists(&self) -> bool {
         self.args.file.exists()
     }
 
+    /// Reads the file as it is, the parser deals with indentation so multi-line strings keep theirs
     pub fn read_file(&self) -> std::io::Result<String> {
         let mut file = std::fs::File::open(&self.args.file)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
 
-        let processed_contents = contents
-            .lines()
-            .map(|line| line.trim())
-            .collect::<Vec<&str>>()
-            .join(" \n ");
-
-        Ok(processed_contents)
+        Ok(contents)
     }
 
-    pub fn run(&mut self) -> std::io::Result<()> {
-        self.get_args();
-
+    /// Runs the file, giving back the status the program should exit with
+    pub fn run(&mut self) -> Result<i32> {
         if !self.file_exists() {
-            println!("File does not exist at this path!");
-            return Ok(());
+            return Err(Error::FileDoesNotExist);
+        }
+
+        if self.args.file.is_dir() {
+            return self.run_project();
         }
 
         let body = self.read_file()?;
+        let mut parser = self.parser(&self.args.file);
+        let report = parser.parse(&body)?;
+
+        Ok(report.exit_code.unwrap_or(0))
+    }
+
+    /// Runs the entry of the project whose root is the file argument
+    ///
+    /// Imports are also looked for in the source directories and dependencies are imported by their name
+    pub fn run_project(&mut self) -> Result<i32> {
+        let project = Project::load(&self.args.file)?;
+        let body = std::fs::read_to_string(&project.entry)?;
+
+        let mut parser = self.parser(&project.entry);
+        add_project(&mut parser, &project);
+        let report = parser.parse(&body)?;
+
+        Ok(report.exit_code.unwrap_or(0))
+    }
+
+    /// A parser set up from the flags to run the file at `path`
+    fn parser(&self, path: &Path) -> Parser {
         let mut parser = Parser::default();
-        parser.parse(&body);
+        parser.set_error_policy(self.args.error_policy);
+        parser.set_permissions(self.args.permissions.clone());
+        parser.set_args(self.args.script_args.clone());
+        if let Some(seed) = self.args.seed {
+            parser.set_seed(seed);
+        }
+        parser.set_path(path);
+        parser
+    }
+}
 
-        Ok(())
+/// The comma separated paths after the `=` of a flag
+///
+/// EXAMPLE:
+///     "--allow-read=./data,./config" -> ["./data", "./config"]
+fn paths(flag: &str) -> impl Iterator<Item = &Path> {
+    let (_, paths) = flag.split_once('=').unwrap_or_default();
+    paths
+        .split(',')
+        .filter(|path| !path.is_empty())
+        .map(Path::new)
+}
+
+/// Makes the source directories of the project and the entries of its dependencies importable from its own files,
+/// and does the same for every dependency all the way down
+fn add_project(parser: &mut Parser, project: &Project) {
+    parser.add_project_root(&project.root);
+    for source in &project.sources {
+        parser.add_project_search_path(&project.root, source);
+    }
+
+    for dependency in &project.dependencies {
+        let entry = &dependency.project.entry;
+        parser.add_project_package(&project.root, &dependency.name, entry);
+        add_project(parser, &dependency.project);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
-    use std::fs::File;
-    use std::io::Write;
+    use crate::temp_dir::TempDir;
 
-    fn setup_test_file(content: &str) -> PathBuf {
-        let path = PathBuf::from("test_file.txt");
-        let mut file = File::create(&path).unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-        path
+    /// Writes the file to a directory of its own, which is removed once the `TempDir` is dropped
+    fn setup_test_file(name: &str, content: &str) -> (TempDir, PathBuf) {
+        let directory = TempDir::new(name.trim_end_matches(".txt"));
+        directory.write(&[(name, content)]);
+        let path = directory.join(name);
+        (directory, path)
     }
 
     #[test]
     fn test_get_args() {
-        // Set the environment variables to simulate command-line arguments
         let args = vec!["program_name".to_string(), "test_file.txt".to_string()];
-        env::set_var("CARGO_BIN_EXE_test", &args[0]);
-        env::set_var("CARGO_BIN_EXE_test_file", &args[1]);
+        let mut cli = CLI::default();
 
-        // Temporarily override the command-line arguments
-        let original_args = env::args().collect::<Vec<_>>();
-        env::set_var("CARGO_BIN_EXE_test", &args[0]);
-        env::set_var("CARGO_BIN_EXE_test_file", &args[1]);
+        cli.get_args(args).unwrap();
 
-        let mut cli = CLI {
-            args: Arguments {
-                program_name: String::new(),
-                file: PathBuf::new(),
-            },
-        };
+        assert_eq!(cli.args.program_name, "program_name");
+        assert_eq!(cli.args.file, PathBuf::from("test_file.txt"));
+        assert_eq!(cli.args.error_policy, ErrorPolicy::Abort);
+    }
 
-        // Simulate the command-line arguments
-        env::set_var("CARGO_BIN_EXE_test", &args[0]);
-        env::set_var("CARGO_BIN_EXE_test_file", &args[1]);
+    #[test]
+    fn test_get_args_continue_on_error() {
+        let args = vec![
+            "program_name".to_string(),
+            "--continue-on-error".to_string(),
+            "test_file.txt".to_string(),
+        ];
+        let mut cli = CLI::default();
 
-        cli.get_args();
+        cli.get_args(args).unwrap();
 
-        assert_eq!(cli.args.program_name, "program_name");
         assert_eq!(cli.args.file, PathBuf::from("test_file.txt"));
+        assert_eq!(cli.args.error_policy, ErrorPolicy::Continue);
+    }
+
+    #[test]
+    fn test_get_args_without_file() {
+        let mut cli = CLI::default();
+
+        let result = cli.get_args(vec!["program_name".to_string()]);
 
-        // Restore the original command-line arguments
-        env::set_var("CARGO_BIN_EXE_test", &original_args[0]);
-        env::set_var("CARGO_BIN_EXE_test_file", &original_args[1]);
+        assert!(matches!(result, Err(Error::ExpectedFilePath)));
     }
+
     #[test]
     fn test_file_exists() {
-        let path = setup_test_file("test content");
+        let (_directory, path) = setup_test_file("test_file_exists.txt", "test content");
         let cli = CLI {
             args: Arguments {
                 program_name: "test_program".to_string(),
-                file: path.clone(),
+                file: path,
+                ..Default::default()
             },
         };
 
         assert!(cli.file_exists());
-
-        std::fs::remove_file(path).unwrap();
     }
 
     #[test]
     fn test_read_file() {
-        let path = setup_test_file("line 1\nline 2\nline 3");
+        let (_directory, path) = setup_test_file("test_read_file.txt", "line 1\n  line 2\nline 3");
         let cli = CLI {
             args: Arguments {
                 program_name: "test_program".to_string(),
-                file: path.clone(),
+                file: path,
+                ..Default::default()
             },
         };
 
         let contents = cli.read_file().unwrap();
-        assert_eq!(contents, "line 1 \n line 2 \n line 3");
-
-        std::fs::remove_file(path).unwrap();
+        assert_eq!(contents, "line 1\n  line 2\nline 3");
     }
 
     #[test]
     fn test_run() {
-        let path = setup_test_file("line 1\nline 2\nline 3");
+        let (_directory, path) = setup_test_file(
+            "test_run.txt",
+            "set x = 1 + 1\n\n\tset y = x * 3\n  y - 1\nset s = \"a\n  b\"",
+        );
         let mut cli = CLI {
             args: Arguments {
                 program_name: "test_program".to_string(),
-                file: path.clone(),
+                file: path,
+                ..Default::default()
             },
         };
 
         let result = cli.run();
         assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_run_stops_on_error() {
+        let (_directory, path) =
+            setup_test_file("test_run_stops_on_error.txt", "1 + 1\n4 / x\n2 + 2");
+        let mut cli = CLI {
+            args: Arguments {
+                program_name: "test_program".to_string(),
+                file: path,
+                ..Default::default()
+            },
+        };
+
+        let result = cli.run();
+        assert!(matches!(
+            result,
+            Err(Error::Parser(parser::Error::InvalidToken(_, _)))
+        ));
+    }
+
+    #[test]
+    fn test_run_project() {
+        let base = TempDir::new("run-project");
+        base.write(&[
+            (
+                "app/project.toml",
+                "[project]\nname = \"app\"\nentry = \"src/main.pl\"\nsources = [\"lib\"]\n\n[dependencies]\nshapes = { path = \"../shapes\" }",
+            ),
+            ("app/src/main.pl", "import helper\nimport shapes\nset total = helper.x + shapes.sides"),
+            ("app/lib/helper.pl", "pub set x = 1"),
+            ("shapes/project.toml", "[project]\nname = \"shapes\"\nentry = \"shapes.pl\""),
+            ("shapes/shapes.pl", "pub const sides = 4"),
+        ]);
+
+        let mut cli = CLI::default();
+        cli.get_args(vec![
+            "program_name".to_string(),
+            base.join("app").display().to_string(),
+        ])
+        .unwrap();
+
+        assert!(cli.run().is_ok());
+    }
+
+    #[test]
+    fn test_run_project_nested_dependencies() {
+        let base = TempDir::new("run-project-nested");
+        base.write(&[
+            (
+                "app/project.toml",
+                "[project]\nname = \"app\"\nentry = \"main.pl\"\n\n[dependencies]\nmid = { path = \"../mid\" }\nutil = { path = \"../util-a\" }",
+            ),
+            ("app/main.pl", "import mid\nimport util\nexit mid.x + util.id"),
+            (
+                "mid/project.toml",
+                "[project]\nname = \"mid\"\nentry = \"mid.pl\"\nsources = [\"lib\"]\n\n[dependencies]\nutil = { path = \"../util-b\" }",
+            ),
+            ("mid/mid.pl", "import util\nimport helper\npub set x = util.id + helper.h"),
+            ("mid/lib/helper.pl", "pub set h = 100"),
+            ("util-a/project.toml", "[project]\nname = \"util\"\nentry = \"util.pl\""),
+            ("util-a/util.pl", "pub const id = 1"),
+            ("util-b/project.toml", "[project]\nname = \"util\"\nentry = \"util.pl\""),
+            ("util-b/util.pl", "pub const id = 20"),
+        ]);
+        let run = |base: &TempDir| {
+            let mut cli = CLI::default();
+            cli.get_args(vec![
+                "program_name".to_string(),
+                "--allow-exit".to_string(),
+                base.join("app").display().to_string(),
+            ])
+            .unwrap();
+            cli.run()
+        };
+
+        // each project gets its own util, and only mid sees its lib
+        assert_eq!(run(&base).unwrap(), 121);
+
+        base.write(&[("app/main.pl", "import helper")]);
+        assert!(matches!(
+            run(&base),
+            Err(Error::Parser(parser::Error::ImportFailed(..)))
+        ));
+    }
+
+    #[test]
+    fn test_run_project_without_manifest() {
+        let root = TempDir::new("no-manifest");
+        let mut cli = CLI {
+            args: Arguments {
+                file: root.to_path_buf(),
+                ..Default::default()
+            },
+        };
+
+        assert!(matches!(
+            cli.run(),
+            Err(Error::Project(project::Error::ManifestNotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn test_get_args_permissions() {
+        let args = vec![
+            "program_name".to_string(),
+            "--allow-read".to_string(),
+            "--allow-write=out,logs".to_string(),
+            "test_file.txt".to_string(),
+        ];
+        let mut cli = CLI::default();
+
+        cli.get_args(args).unwrap();
+
+        let permissions = &cli.args.permissions;
+        assert_eq!(permissions.read, Scope::All);
+        assert!(permissions.can_write(Path::new("logs/today.txt")));
+        assert!(!permissions.can_write(Path::new("test_file.txt")));
+    }
+
+    #[test]
+    fn test_get_args_script_args() {
+        let args = vec![
+            "program_name".to_string(),
+            "--allow-args".to_string(),
+            "test_file.txt".to_string(),
+            "--verbose".to_string(),
+            "data.csv".to_string(),
+        ];
+        let mut cli = CLI::default();
+
+        cli.get_args(args).unwrap();
+
+        assert!(cli.args.permissions.args);
+        assert_eq!(cli.args.file, PathBuf::from("test_file.txt"));
+        assert_eq!(cli.args.script_args, vec!["--verbose", "data.csv"]);
+    }
+
+    #[test]
+    fn test_get_args_unknown_flag() {
+        let args = vec![
+            "program_name".to_string(),
+            "--verbose".to_string(),
+            "test_file.txt".to_string(),
+        ];
+        let mut cli = CLI::default();
+
+        assert!(matches!(
+            cli.get_args(args),
+            Err(Error::UnexpectedArguments(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_args_seed() {
+        let mut cli = CLI::default();
+        cli.get_args(vec![
+            "program_name".to_string(),
+            "--seed=42".to_string(),
+            "test_file.txt".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(cli.args.seed, Some(42));
+
+        assert!(matches!(
+            cli.get_args(vec![
+                "program_name".to_string(),
+                "--seed=-1".to_string(),
+                "test_file.txt".to_string(),
+            ]),
+            Err(Error::InvalidSeed(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_exit_code() {
+        let (_directory, path) = setup_test_file("test_run_exit_code.txt", "exit 4\nprintln 1");
+        let mut cli = CLI::default();
+        cli.get_args(vec![
+            "program_name".to_string(),
+            "--allow-exit".to_string(),
+            path.display().to_string(),
+        ])
+        .unwrap();
 
-        std::fs::remove_file(path).unwrap();
+        assert_eq!(cli.run().unwrap(), 4);
     }
 }
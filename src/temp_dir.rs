@@ -0,0 +1,42 @@
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// An empty directory of its own for a test, removed along with everything in it once dropped
+///
+/// The name has the process id in it, so test runs going on at the same time don't share it
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(test: &str) -> TempDir {
+        let name = format!("prog-lang-{}-{}", test, std::process::id());
+        let path = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    /// Writes the files, creating the directories they are in
+    pub fn write(&self, files: &[(&str, &str)]) {
+        for (name, body) in files {
+            let path = self.path.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, body).unwrap();
+        }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}